@@ -0,0 +1,126 @@
+use std::{path::PathBuf, time::Duration};
+
+use input_linux::{Key, KeyState};
+
+use crate::{
+    device::{OutputDevice, TouchState},
+    point::{Point, Target},
+};
+
+pub struct Touch {
+    pub fingers: u8,
+    /// Horizontal distance between fingers, from 0.0 to 1.0 of the output
+    pub spacing: f64,
+}
+
+/// The virtual device that clicks at points, a tablet or a touchscreen
+pub struct Absolute {
+    pub device: OutputDevice,
+    /// When set, the device is a touchscreen
+    pub touch: Option<Touch>,
+    pub swipe_steps: u32,
+    pub swipe_cooldown: Duration,
+
+    tracking_id: i32,
+    /// The swipe in progress, with its last step
+    swipe: Option<(Point, Point, u32)>,
+    /// Where the device touches now
+    position: Point,
+}
+
+impl Absolute {
+    pub fn open(touch: Option<Touch>, swipe_steps: u32, swipe_cooldown: Duration) -> Self {
+        let device = if touch.is_some() {
+            let mut device =
+                OutputDevice::uinput_open(PathBuf::from("/dev/uinput"), "TheClicker Touchscreen")
+                    .unwrap();
+            device.add_touchscreen_attributes();
+            device
+        } else {
+            let mut device =
+                OutputDevice::uinput_open(PathBuf::from("/dev/uinput"), "TheClicker Tablet")
+                    .unwrap();
+            device.add_tablet_attributes();
+            device
+        };
+        device.create();
+
+        Self {
+            device,
+            touch,
+            swipe_steps,
+            swipe_cooldown,
+            tracking_id: 0,
+            swipe: None,
+            position: Point { x: 0.0, y: 0.0 },
+        }
+    }
+
+    /// Presses `keys` at the target, a touchscreen ignores the keys and taps
+    pub fn press(&mut self, target: Target, keys: &[Key]) {
+        let from = match target {
+            Target::Tap(point) => point,
+            Target::Swipe(from, to) => {
+                self.swipe = Some((from, to, 0));
+                from
+            }
+        };
+        self.position = from;
+
+        if let Some(touch) = &self.touch {
+            let fingers = touch.fingers as i32;
+            self.device
+                .send_touch(&self.fingers(from), TouchState::Down(self.tracking_id));
+            self.tracking_id = (self.tracking_id + fingers) & u16::MAX as i32;
+        } else {
+            self.device.send_tablet_keys(from, keys, KeyState::PRESSED);
+        }
+    }
+
+    /// If the pressed target is a swipe, that has steps left
+    pub fn swiping(&self) -> bool {
+        self.swipe.is_some()
+    }
+
+    /// Moves by one step of the swipe
+    pub fn step(&mut self) {
+        let Some((from, to, step)) = &mut self.swipe else {
+            return;
+        };
+        *step += 1;
+        let point = from.lerp(*to, *step as f64 / self.swipe_steps as f64);
+        if *step >= self.swipe_steps {
+            self.swipe = None;
+        }
+        self.position = point;
+
+        if self.touch.is_some() {
+            self.device
+                .send_touch(&self.fingers(point), TouchState::Move);
+        } else {
+            self.device.send_tablet_position(point);
+        }
+    }
+
+    pub fn release(&mut self, keys: &[Key]) {
+        self.swipe = None;
+
+        if self.touch.is_some() {
+            self.device
+                .send_touch(&self.fingers(self.position), TouchState::Up);
+        } else {
+            self.device
+                .send_tablet_keys(self.position, keys, KeyState::RELEASED);
+        }
+    }
+
+    fn fingers(&self, point: Point) -> Vec<Point> {
+        let Some(touch) = &self.touch else {
+            return vec![point];
+        };
+
+        (0..touch.fingers)
+            .map(|finger| point.offset(touch.spacing * finger as f64, 0.0))
+            .collect()
+    }
+}
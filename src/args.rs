@@ -1,73 +1,411 @@
-use clap::Parser;
+use std::path::PathBuf;
+
+use clap::{Parser, ValueEnum};
+
+use crate::{bind::Bind, device::MAX_NAME_LEN, point::Target};
+
+// The defaults of `run`, the wizard uses them too
+pub const SCROLL_COOLDOWN: u64 = 100;
+pub const SCROLL_AMOUNT: i32 = 1;
+pub const JIGGLE_INTERVAL: u64 = 30000;
+pub const JIGGLE_AMPLITUDE: i32 = 1;
+pub const MOTION_DX: i32 = 100;
+pub const MOTION_DY: i32 = 0;
+pub const MOTION_RADIUS: i32 = 50;
+pub const MOTION_STEPS: u32 = 20;
+pub const MOTION_COOLDOWN: u64 = 10;
+pub const FINGERS: u8 = 1;
+pub const FINGER_SPACING: f64 = 0.05;
+pub const SWIPE_STEPS: u32 = 10;
+pub const SWIPE_COOLDOWN: u64 = 10;
+pub const COOLDOWN: u64 = 25;
+pub const COOLDOWN_PRESS_RELEASE: u64 = 0;
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum ScrollDirection {
+    Up,
+    #[default]
+    Down,
+    Left,
+    Right,
+}
+
+impl std::fmt::Display for ScrollDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.to_possible_value().unwrap().get_name())
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum Bus {
+    Pci,
+    Usb,
+    Bluetooth,
+    Virtual,
+    I8042,
+    I2c,
+    Host,
+    Spi,
+}
+
+impl Bus {
+    pub fn bustype(self) -> u16 {
+        use input_linux::sys;
+        match self {
+            Bus::Pci => sys::BUS_PCI,
+            Bus::Usb => sys::BUS_USB,
+            Bus::Bluetooth => sys::BUS_BLUETOOTH,
+            Bus::Virtual => sys::BUS_VIRTUAL,
+            Bus::I8042 => sys::BUS_I8042,
+            Bus::I2c => sys::BUS_I2C,
+            Bus::Host => sys::BUS_HOST,
+            Bus::Spi => sys::BUS_SPI,
+        }
+    }
+
+    pub fn from_bustype(bustype: u16) -> Option<Self> {
+        Self::value_variants()
+            .iter()
+            .copied()
+            .find(|bus| bus.bustype() == bustype)
+    }
+}
+
+impl std::fmt::Display for Bus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.to_possible_value().unwrap().get_name())
+    }
+}
+
+fn parse_hex(value: &str) -> Result<u16, String> {
+    let value = value.trim_start_matches("0x");
+    u16::from_str_radix(value, 16).map_err(|err| format!("{value:?} is not a hex number: {err}"))
+}
+
+/// uinput refuses the names of `UINPUT_MAX_NAME_SIZE` bytes or more, with the null terminator
+fn parse_name(value: &str) -> Result<String, String> {
+    if value.len() > MAX_NAME_LEN {
+        return Err(format!(
+            "{} bytes is too long, the name can have at most {MAX_NAME_LEN} bytes",
+            value.len()
+        ));
+    }
+    Ok(value.to_owned())
+}
+
+/// How the virtual device will be seen by the compositor and udev
+#[derive(clap::Args, Debug, Default, Clone)]
+pub struct Identity {
+    /// The name of the virtual device, at most 79 bytes
+    #[arg(long, value_parser = parse_name)]
+    pub name: Option<String>,
+
+    /// The bus type of the virtual device
+    #[arg(long, value_enum)]
+    pub bus: Option<Bus>,
+
+    /// The vendor id of the virtual device, in hex
+    #[arg(long, value_parser = parse_hex)]
+    pub vendor: Option<u16>,
+
+    /// The product id of the virtual device, in hex
+    #[arg(long, value_parser = parse_hex)]
+    pub product: Option<u16>,
+
+    /// The version of the virtual device, in hex
+    #[arg(long, value_parser = parse_hex)]
+    pub device_version: Option<u16>,
+
+    /// The physical location of the virtual device, like `usb-0000:00:14.0-1/input0`
+    #[arg(long)]
+    pub phys: Option<String>,
+}
+
+impl std::fmt::Display for Identity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(name) = &self.name {
+            write!(f, "--name {name:?} ")?;
+        }
+        if let Some(bus) = self.bus {
+            write!(f, "--bus {bus} ")?;
+        }
+        if let Some(vendor) = self.vendor {
+            write!(f, "--vendor {vendor:04x} ")?;
+        }
+        if let Some(product) = self.product {
+            write!(f, "--product {product:04x} ")?;
+        }
+        if let Some(version) = self.device_version {
+            write!(f, "--device-version {version:04x} ")?;
+        }
+        if let Some(phys) = &self.phys {
+            write!(f, "--phys {phys:?} ")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum MotionPattern {
+    /// Moves by --motion-dx/--motion-dy every cycle
+    #[default]
+    Line,
+    /// Traces a circle of --motion-radius, that starts and ends at the cursor
+    Circle,
+    /// Moves by --motion-dx/--motion-dy, while going up and down by --motion-radius
+    Zigzag,
+    /// Every step moves --motion-radius in a random direction
+    RandomWalk,
+}
+
+impl std::fmt::Display for MotionPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.to_possible_value().unwrap().get_name())
+    }
+}
 
 #[derive(clap::Subcommand, Debug)]
 pub enum Command {
     Run {
         /// Device name or path when the first character is `/`
-        /// (first looks for exact match, then takes the first device that contains the name)
-        #[arg(short = 'd')]
-        device_query: String,
+        /// (first looks for exact match, then for the device that contains the name)
+        /// Or `046d:c52b` vendor:product, `phys:...`, `uniq:...`, `link:<by-id or by-path name>`, `re:<regex of the name>`, `type:mouse`
+        /// When more devices match, they are listed and none is used
+        /// Can be repeated to read from more devices, a bind like `275@1` is only used on the second device
+        #[arg(short = 'd', required = true)]
+        device_query: Vec<String>,
 
         /// Bind left autoclicker to keycode
         /// Mouse: 275 ButtonSide
         /// Keyboard: 26 LeftBrace
+        /// Without any bind, the one above for the type of the first device is used
         #[arg(short)]
-        left_bind: Option<u16>,
+        left_bind: Option<Bind>,
 
         /// Bind middle autoclicker to keycode
         /// Mouse: 274 ButtonMiddle
         /// Keyboard: 43 Backslash
         #[arg(short)]
-        middle_bind: Option<u16>,
+        middle_bind: Option<Bind>,
 
         /// Bind right autoclicker to keycode
         /// Mouse: 276 ButtonExtra
         /// Keyboard: 27 RightBrace
+        /// Without any bind, the one above for the type of the first device is used
         #[arg(short)]
-        right_bind: Option<u16>,
+        right_bind: Option<Bind>,
 
         /// Bind lock/unlock to keycode
         /// Mouse: 274 ButtonMiddle
         /// With this you can bind to the left, middle and right button, and the bindings will only be used when is unlocked.
         /// Useful for mouses without side buttons.
         #[arg(short = 'T')]
-        lock_unlock_bind: Option<u16>,
+        lock_unlock_bind: Option<Bind>,
+
+        /// Bind auto-scroll to keycode
+        /// While active, the wheel will be scrolled in the --scroll-direction
+        #[arg(short = 's')]
+        scroll_bind: Option<Bind>,
+
+        /// The direction of the auto-scroll
+        #[arg(long, value_enum, default_value_t = ScrollDirection::default())]
+        scroll_direction: ScrollDirection,
+
+        /// Set the cooldown in milliseconds, between scroll ticks
+        #[arg(long, default_value_t = SCROLL_COOLDOWN)]
+        scroll_cooldown: u64,
+
+        /// How much to scroll every tick, in wheel detents
+        /// With --scroll-hi-res in 1/120 of a detent
+        #[arg(long, default_value_t = SCROLL_AMOUNT)]
+        scroll_amount: i32,
+
+        /// Emit high-resolution wheel events, for smooth scrolling
+        #[arg(long, default_value_t = false)]
+        scroll_hi_res: bool,
+
+        /// Bind the mouse jiggler to keycode
+        /// While active, the cursor is moved out and back every --jiggle-interval, so the machine does not go idle
+        #[arg(short = 'j')]
+        jiggle_bind: Option<Bind>,
+
+        /// Set the interval in milliseconds, between jiggles
+        #[arg(long, default_value_t = JIGGLE_INTERVAL)]
+        jiggle_interval: u64,
+
+        /// How many pixels the cursor is moved by a jiggle
+        #[arg(long, default_value_t = JIGGLE_AMPLITUDE)]
+        jiggle_amplitude: i32,
+
+        /// Tap this keycode instead of moving the cursor
+        /// Keyboard: 186 F16
+        #[arg(long)]
+        jiggle_key: Option<u16>,
+
+        /// Bind motion to keycode
+        /// While active, the cursor follows the --motion pattern, between the clicks of the other autoclickers
+        #[arg(short = 'M')]
+        motion_bind: Option<Bind>,
+
+        /// The pattern that the cursor will follow
+        #[arg(long, value_enum, default_value_t = MotionPattern::default())]
+        motion: MotionPattern,
+
+        /// Horizontal distance in pixels of a line or zigzag cycle
+        #[arg(long, default_value_t = MOTION_DX, allow_negative_numbers = true)]
+        motion_dx: i32,
+
+        /// Vertical distance in pixels of a line or zigzag cycle
+        #[arg(long, default_value_t = MOTION_DY, allow_negative_numbers = true)]
+        motion_dy: i32,
+
+        /// Radius of the circle, height of the zigzag or step length of the random walk, in pixels
+        #[arg(long, default_value_t = MOTION_RADIUS)]
+        motion_radius: i32,
+
+        /// How many steps a motion cycle has
+        #[arg(long, default_value_t = MOTION_STEPS, value_parser = clap::value_parser!(u32).range(1..))]
+        motion_steps: u32,
+
+        /// Set the cooldown in milliseconds, between motion steps
+        #[arg(long, default_value_t = MOTION_COOLDOWN)]
+        motion_cooldown: u64,
+
+        /// Drag mode, the left button is pressed at the start of every motion cycle and released at the end
+        #[arg(long, default_value_t = false)]
+        drag: bool,
+
+        /// Click at this point, `x,y` from 0.0 to 1.0 of the output, or swipe between two points `x,y:x,y`
+        /// Can be repeated to click the points in rotation
+        /// This creates a second virtual device, an absolute tablet, that will be used for the clicks
+        #[arg(long = "point")]
+        targets: Vec<Target>,
+
+        /// The absolute device will be a multitouch touchscreen instead of a tablet, every click will be a tap
+        #[arg(long, default_value_t = false, requires = "targets")]
+        touchscreen: bool,
+
+        /// How many fingers a touchscreen tap uses
+        #[arg(long, default_value_t = FINGERS, value_parser = clap::value_parser!(u8).range(1..=crate::device::TOUCH_SLOTS as i64))]
+        fingers: u8,
+
+        /// The horizontal distance between fingers, from 0.0 to 1.0 of the output
+        #[arg(long, default_value_t = FINGER_SPACING)]
+        finger_spacing: f64,
+
+        /// In how many steps a swipe moves between the two points
+        #[arg(long, default_value_t = SWIPE_STEPS, value_parser = clap::value_parser!(u32).range(1..))]
+        swipe_steps: u32,
+
+        /// Set the cooldown in milliseconds, between swipe steps
+        #[arg(long, default_value_t = SWIPE_COOLDOWN)]
+        swipe_cooldown: u64,
 
         /// Hold mode, when a keybind is pressed the autoclicker will be active until the keybind release
         #[arg(short = 'H', default_value_t = false)]
         hold: bool,
 
-        /// This will grab the device,
-        #[arg(long, default_value_t = false)]
-        grab: bool,
+        /// This will grab the devices, their input is sent by TheClicker without the bindings.
+        /// `--grab` grabs every `-d` device, `--grab 0 --grab 2` or `--grab 0,2` only the devices at these indexes
+        #[arg(long, num_args = 0.., value_delimiter = ',')]
+        grab: Option<Vec<u8>>,
+
+        /// With --grab, the input of every grabbed device is sent by a separate virtual device, named after the grabbed device,
+        /// so the compositor can tell it apart from the clicks
+        #[arg(long, default_value_t = false, requires = "grab")]
+        split: bool,
+
+        /// With --grab, the virtual device will have the name, ids and phys of the first grabbed device,
+        /// so the compositor and udev rules for the device keep matching, the identity options take precedence
+        #[arg(
+            long,
+            default_value_t = false,
+            requires = "grab",
+            conflicts_with = "split"
+        )]
+        mirror: bool,
 
         /// Set the cooldown in milliseconds
-        #[arg(short, default_value_t = 25)]
+        #[arg(short, default_value_t = COOLDOWN)]
         cooldown: u64,
 
         /// Set cooldown in milliseconds, between press and release
-        #[arg(short = 'C', default_value_t = 0)]
+        #[arg(short = 'C', default_value_t = COOLDOWN_PRESS_RELEASE)]
         cooldown_press_release: u64,
     },
     RunLegacy {
         /// Device name or path when the first character is `/`
-        /// (first looks for exact match, then takes the first device that contains the name)
+        /// (first looks for exact match, then for the device that contains the name)
+        /// Or `046d:c52b` vendor:product, `phys:...`, `uniq:...`, `link:<by-id or by-path name>`, `re:<regex of the name>`, `type:mouse`
+        /// When more devices match, they are listed and none is used
         #[arg(short = 'd')]
         device_query: String,
 
         /// Set the cooldown in milliseconds
-        #[arg(short, default_value_t = 25)]
+        #[arg(short, default_value_t = COOLDOWN)]
         cooldown: u64,
 
         /// Set cooldown in milliseconds, between press and release
-        #[arg(short = 'C', default_value_t = 0)]
+        #[arg(short = 'C', default_value_t = COOLDOWN_PRESS_RELEASE)]
         cooldown_press_release: u64,
     },
+    /// Lists the input devices, with their ids, type and buttons
+    List {
+        /// Print the devices as a JSON array
+        #[arg(long, default_value_t = false)]
+        json: bool,
+    },
+    /// Prints the keycode of every key or button pressed, with the flag that binds it
+    Listen {
+        /// The device to listen to, the same queries as for `run -d`
+        #[arg(short = 'd', required_unless_present = "all")]
+        device_query: Option<String>,
+
+        /// Listen to every device with keys or buttons, to find which device a button belongs to
+        #[arg(long, default_value_t = false, conflicts_with = "device_query")]
+        all: bool,
+    },
+    /// Checks uinput, the permissions and the grabs, and prints how to fix what fails
+    Doctor {
+        /// Only check the grab of this device, the same queries as for `run -d`
+        #[arg(short = 'd')]
+        device_query: Option<String>,
+    },
+    /// Sets up the system to run TheClicker without root
+    Setup {
+        #[command(subcommand)]
+        target: SetupTarget,
+    },
+}
+
+#[derive(clap::Subcommand, Debug)]
+pub enum SetupTarget {
+    /// Writes a udev rule that gives your group access to /dev/uinput,
+    /// instead of running as root or joining the input group
+    Udev {
+        /// Also give the user logged in on the seat access to this device, and to no other,
+        /// with TAG+="uaccess", the same queries as for `run -d`
+        #[arg(short = 'd')]
+        device_query: Option<String>,
+
+        /// The group that gets access to /dev/uinput, the primary group of the user that ran sudo by default
+        #[arg(long)]
+        group: Option<String>,
+
+        /// Print the rule instead of writing it
+        #[arg(long, default_value_t = false)]
+        print: bool,
+
+        /// Where to write the rule, it must sort before 73-seat-late.rules for TAG+="uaccess" to work
+        #[arg(long, default_value = "/etc/udev/rules.d/70-theclicker.rules")]
+        path: PathBuf,
+    },
 }
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 pub struct Args {
+    /// Print the events of the input devices in frames, decoded like evtest, and the events written to the virtual devices
     #[arg(long, default_value_t = false)]
     pub debug: bool,
 
@@ -75,6 +413,64 @@ pub struct Args {
     #[arg(long, default_value_t = false)]
     pub beep: bool,
 
+    /// When running as root, switch to this user and its group after opening the devices,
+    /// the user that ran sudo by default, `--user root` keeps root.
+    /// Reconnecting an unplugged device then needs that user to have access to it
+    #[arg(long)]
+    pub user: Option<String>,
+
+    /// After opening the devices, forbid opening files with Landlock and the syscalls
+    /// that the clicker does not need with seccomp. An unplugged device cannot be reopened then
+    #[arg(long, default_value_t = false)]
+    pub sandbox: bool,
+
+    #[command(flatten)]
+    pub identity: Identity,
+
     #[command(subcommand)]
     pub command: Option<Command>,
 }
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    fn grab(args: &[&str]) -> Option<Vec<u8>> {
+        let args = Args::try_parse_from(
+            ["theclicker", "run", "-d", "a", "-d", "b"]
+                .iter()
+                .chain(args),
+        )
+        .unwrap();
+        match args.command {
+            Some(Command::Run { grab, .. }) => grab,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn grab_every_device_or_some() {
+        assert_eq!(grab(&[]), None);
+        assert_eq!(grab(&["--grab"]), Some(vec![]));
+        assert_eq!(grab(&["--grab", "-H"]), Some(vec![]));
+        assert_eq!(grab(&["--grab", "1"]), Some(vec![1]));
+        assert_eq!(grab(&["--grab", "0", "--grab", "1"]), Some(vec![0, 1]));
+        assert_eq!(grab(&["--grab", "0,1", "--split"]), Some(vec![0, 1]));
+    }
+
+    #[test]
+    fn name_fits_uinput() {
+        let name = |name: &str| {
+            Args::try_parse_from(["theclicker", "--name", name])
+                .ok()
+                .and_then(|args| args.identity.name)
+        };
+        assert_eq!(name("TheClicker"), Some("TheClicker".to_owned()));
+        assert_eq!(name(&"a".repeat(79)), Some("a".repeat(79)));
+        assert_eq!(name(&"a".repeat(80)), None);
+        // Bytes, not characters
+        assert_eq!(name(&"é".repeat(40)), None);
+    }
+}
@@ -0,0 +1,104 @@
+use std::str::FromStr;
+
+/// A keycode, from any input device or only from one of them
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bind {
+    pub code: u16,
+    /// The index of the `-d` device, in the order they were given
+    pub device: Option<u8>,
+}
+
+impl Bind {
+    /// The keycode, when the bind is used on the device at `index`
+    pub fn on(self, index: usize) -> Option<u16> {
+        match self.device {
+            Some(device) if device as usize != index => None,
+            _ => Some(self.code),
+        }
+    }
+}
+
+impl FromStr for Bind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (code, device) = match s.split_once('@') {
+            Some((code, device)) => (code, Some(device)),
+            None => (s, None),
+        };
+
+        Ok(Self {
+            code: code
+                .trim()
+                .parse()
+                .map_err(|err| format!("{code:?} is not a keycode: {err}"))?,
+            device: device
+                .map(|device| {
+                    device
+                        .trim()
+                        .parse()
+                        .map_err(|err| format!("{device:?} is not a device index: {err}"))
+                })
+                .transpose()?,
+        })
+    }
+}
+
+impl std::fmt::Display for Bind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code)?;
+        if let Some(device) = self.device {
+            write!(f, "@{device}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_display() {
+        let bind = "275".parse::<Bind>().unwrap();
+        assert_eq!(
+            bind,
+            Bind {
+                code: 275,
+                device: None
+            }
+        );
+        assert_eq!(bind.to_string(), "275");
+
+        let bind = " 276 @ 1 ".parse::<Bind>().unwrap();
+        assert_eq!(
+            bind,
+            Bind {
+                code: 276,
+                device: Some(1)
+            }
+        );
+        assert_eq!(bind.to_string(), "276@1");
+        assert_eq!(bind.to_string().parse::<Bind>(), Ok(bind));
+    }
+
+    #[test]
+    fn invalid() {
+        for bind in [
+            "", "x", "-1", "70000", "275@", "275@x", "275@256", "275@1@2",
+        ] {
+            assert!(bind.parse::<Bind>().is_err(), "{bind}");
+        }
+    }
+
+    #[test]
+    fn on() {
+        let any = "275".parse::<Bind>().unwrap();
+        assert_eq!(any.on(0), Some(275));
+        assert_eq!(any.on(3), Some(275));
+
+        let second = "275@1".parse::<Bind>().unwrap();
+        assert_eq!(second.on(0), None);
+        assert_eq!(second.on(1), Some(275));
+    }
+}
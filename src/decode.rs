@@ -0,0 +1,37 @@
+use input_linux::sys::{EV_KEY, EV_SYN, SYN_REPORT, input_event};
+
+use crate::names::{code_name, type_name};
+
+/// An event printed like evtest, `1700000000.123456 EV_KEY BTN_SIDE pressed`,
+/// a `SYN_REPORT` is printed as the end of the frame
+pub struct Decoded<'a>(pub &'a input_event);
+
+impl std::fmt::Display for Decoded<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let event = self.0;
+        write!(f, "{}.{:06} ", event.time.tv_sec, event.time.tv_usec)?;
+
+        if event.type_ == EV_SYN as u16 && event.code == SYN_REPORT as u16 {
+            return f.write_str("-------------- SYN_REPORT ------------");
+        }
+
+        match type_name(event.type_) {
+            Some(name) => write!(f, "{name} ")?,
+            None => write!(f, "type {:#x} ", event.type_)?,
+        }
+        match code_name(event.type_, event.code) {
+            Some(name) => write!(f, "{name} ")?,
+            None => write!(f, "code {:#x} ", event.code)?,
+        }
+        if event.type_ == EV_KEY as u16 {
+            match event.value {
+                0 => f.write_str("released"),
+                1 => f.write_str("pressed"),
+                2 => f.write_str("repeat"),
+                value => write!(f, "{value}"),
+            }
+        } else {
+            write!(f, "{}", event.value)
+        }
+    }
+}
@@ -1,27 +1,61 @@
 use std::{
+    collections::HashMap,
+    ffi::CString,
     fs::{self, File},
     io,
     os::fd::AsRawFd,
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::exit,
     time::SystemTime,
 };
 
 use input_linux::{
-    EvdevHandle, EventKind, EventTime, InputEvent, InputId, Key, KeyEvent, KeyState,
-    SynchronizeEvent, UInputHandle,
-    sys::{BUS_USB, input_event},
+    AbsoluteAxis, AbsoluteEvent, AbsoluteInfo, AbsoluteInfoSetup, AutorepeatEvent, AutorepeatKind,
+    Bitmask, EvdevHandle, EventKind, EventTime, InputEvent, InputId, InputProperty, Key, KeyEvent,
+    KeyState, RelativeAxis, RelativeEvent, SynchronizeEvent, UInputHandle,
+    bitmask::BitmaskTrait,
+    sys::{self, BUS_USB, input_event, repeat_settings},
 };
 
-use crate::{choose_usize, choose_yes};
+use crate::{
+    args::{Identity, ScrollDirection},
+    choose_usize, choose_yes,
+    decode::Decoded,
+    point::Point,
+    query::DeviceQuery,
+};
+
+/// The value of one wheel detent in high-resolution wheel events
+pub const WHEEL_HI_RES_DETENT: i32 = 120;
+
+/// The maximum of the absolute axes of the virtual tablet and touchscreen
+const TABLET_MAX: i32 = 32767;
+
+/// How many fingers the virtual touchscreen can track at the same time
+pub const TOUCH_SLOTS: u8 = 10;
+
+pub enum TouchState {
+    /// The fingers touch the surface, the value is the tracking id of the first finger
+    Down(i32),
+    Move,
+    Up,
+}
+
+/// The longest name of a virtual device, uinput needs the null terminator too
+pub const MAX_NAME_LEN: usize = sys::UINPUT_MAX_NAME_SIZE as usize - 1;
 
 const VENDOR: u16 = 0x3232;
 const VERSION: u16 = 0x1234;
 const PRODUCT: u16 = 0x5678;
 
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum DeviceType {
     Mouse,
     Keyboard,
+    Touchpad,
+    Touchscreen,
+    Gamepad,
+    Unknown,
 }
 
 impl DeviceType {
@@ -32,6 +66,104 @@ impl DeviceType {
     pub fn is_keyboard(&self) -> bool {
         matches!(self, DeviceType::Keyboard)
     }
+
+    /// The keys that suit the left, middle and right autoclicker on this kind of device
+    pub fn suggested_binds(&self) -> [Option<Key>; 3] {
+        match self {
+            DeviceType::Mouse => [Some(Key::ButtonSide), None, Some(Key::ButtonExtra)],
+            DeviceType::Keyboard => [
+                Some(Key::LeftBrace),
+                Some(Key::Backslash),
+                Some(Key::RightBrace),
+            ],
+            _ => [None; 3],
+        }
+    }
+}
+
+impl From<&Capabilities> for DeviceType {
+    fn from(capabilities: &Capabilities) -> Self {
+        if capabilities.absolute.get(AbsoluteAxis::MultitouchPositionX) {
+            if capabilities.properties.get(InputProperty::Direct) {
+                DeviceType::Touchscreen
+            } else {
+                DeviceType::Touchpad
+            }
+        } else if capabilities.keys.get(Key::ButtonSouth) {
+            DeviceType::Gamepad
+        } else if capabilities.relative.get(RelativeAxis::X)
+            && capabilities.keys.get(Key::ButtonLeft)
+        {
+            DeviceType::Mouse
+        } else if capabilities.keys.get(Key::A) {
+            DeviceType::Keyboard
+        } else {
+            DeviceType::Unknown
+        }
+    }
+}
+
+impl std::str::FromStr for DeviceType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            DeviceType::Mouse,
+            DeviceType::Keyboard,
+            DeviceType::Touchpad,
+            DeviceType::Touchscreen,
+            DeviceType::Gamepad,
+            DeviceType::Unknown,
+        ]
+        .into_iter()
+        .find(|device_type| device_type.to_string() == s)
+        .ok_or_else(|| format!("Unknown device type: {s:?}"))
+    }
+}
+
+impl std::fmt::Display for DeviceType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            DeviceType::Mouse => "mouse",
+            DeviceType::Keyboard => "keyboard",
+            DeviceType::Touchpad => "touchpad",
+            DeviceType::Touchscreen => "touchscreen",
+            DeviceType::Gamepad => "gamepad",
+            DeviceType::Unknown => "unknown",
+        })
+    }
+}
+
+/// What a device can report, from its evdev bits
+#[derive(Default)]
+pub struct Capabilities {
+    pub events: Bitmask<EventKind>,
+    pub keys: Bitmask<Key>,
+    pub relative: Bitmask<RelativeAxis>,
+    pub absolute: Bitmask<AbsoluteAxis>,
+    pub properties: Bitmask<InputProperty>,
+}
+
+impl Capabilities {
+    /// The mouse, joystick and gamepad buttons, without the keyboard keys
+    pub fn buttons(&self) -> impl Iterator<Item = Key> {
+        self.keys.iter().filter(|key| {
+            let code = *key as i32;
+            (sys::BTN_MISC..sys::KEY_OK).contains(&code)
+                || (sys::BTN_TRIGGER_HAPPY..=sys::BTN_TRIGGER_HAPPY40).contains(&code)
+        })
+    }
+}
+
+/// What tells a device apart from the next device that gets its `eventN`,
+/// without the phys, that changes when the device is plugged in another port
+#[derive(Debug, PartialEq)]
+pub struct DeviceIdentity {
+    name: String,
+    bustype: u16,
+    vendor: u16,
+    product: u16,
+    uniq: String,
 }
 
 pub struct InputDevice {
@@ -42,21 +174,26 @@ pub struct InputDevice {
 }
 
 impl InputDevice {
-    pub fn dev_open(mut path: PathBuf) -> Result<Self, String> {
-        if path.is_symlink() {
-            // This means that the path is /dev/input/by-path/{ } or /dev/input/by-id/{ }
-            path = PathBuf::from("/dev/input")
-                .join(std::fs::read_link(&path).unwrap().file_name().unwrap());
-        }
-
-        let file = match File::open(&path) {
-            Ok(file) => file,
+    pub fn dev_open(path: PathBuf) -> Result<Self, String> {
+        match Self::try_open(path) {
+            Ok(device) => Ok(device),
             Err(err) => {
                 println!("Error: {}", err);
                 println!("Invalid device OR Not having access to the file, try as root!");
+                println!("Run `theclicker doctor` to see what is missing");
                 exit(1);
             }
-        };
+        }
+    }
+
+    /// Like `dev_open`, but the error is returned instead of terminating
+    pub fn try_open(mut path: PathBuf) -> io::Result<Self> {
+        if path.is_symlink() {
+            // This means that the path is /dev/input/by-path/{ } or /dev/input/by-id/{ }
+            path = PathBuf::from("/dev/input").join(fs::read_link(&path)?.file_name().unwrap());
+        }
+
+        let file = File::open(&path)?;
 
         let handler = EvdevHandle::new(file);
 
@@ -73,56 +210,58 @@ impl InputDevice {
         })
     }
 
-    pub fn devices() -> Vec<InputDevice> {
-        fs::read_dir("/dev/input")
-            .unwrap()
-            .filter_map(|res| res.ok())
-            .filter_map(|entry| {
-                if let Ok(ty) = entry.file_type()
-                    && ty.is_dir()
-                {
-                    return None;
-                }
-
-                if entry.path().file_name().unwrap().to_str().unwrap() == "mice" {
-                    return None;
-                }
-
-                InputDevice::dev_open(entry.path()).ok()
-            })
-            .collect::<Vec<InputDevice>>()
-    }
-
-    pub fn find_device(device_name: &str) -> Option<InputDevice> {
-        let devices = Self::devices();
-
-        for device in devices {
-            if device.name.trim() == device_name {
-                return Some(device);
-            }
+    /// Opens the device of the query again, after it was disconnected,
+    /// the devices in `ignore` are skipped, like the virtual devices of TheClicker.
+    /// `None` when the device is not connected yet, the error when it cannot be opened,
+    /// or when another device has its node now
+    pub fn reopen(
+        device_query: &str,
+        ignore: &[PathBuf],
+        identity: &DeviceIdentity,
+    ) -> Option<io::Result<InputDevice>> {
+        let path = if device_query.starts_with('/') {
+            let path = PathBuf::from(device_query);
+            // The by-id and by-path links are resolved, like in `try_open`
+            fs::canonicalize(&path).ok()?
+        } else {
+            let query = device_query.parse::<DeviceQuery>().ok()?;
+            let devices = DeviceInfo::enumerate()
+                .into_iter()
+                .filter(|info| !ignore.contains(&info.path))
+                .collect();
+            query.find(devices).ok()?.path
+        };
+        if ignore.contains(&path) {
+            return None;
         }
 
-        let devices = Self::devices();
-        devices
-            .into_iter()
-            .find(|device| device.name.trim().contains(device_name))
+        let device = match InputDevice::try_open(path) {
+            Ok(device) => device,
+            Err(err) => return Some(Err(err)),
+        };
+        if device.identity() != *identity {
+            return Some(Err(io::Error::other(format!(
+                "{} is another device now, {}",
+                device.path.display(),
+                device.name
+            ))));
+        }
+        Some(Ok(device))
     }
 
     pub fn select_device() -> InputDevice {
         loop {
-            let mut devices = Self::devices();
+            // The legacy `mouseN` are offered for the PS/2 mice, the `jsN` are not supported
+            let mut devices = DeviceInfo::enumerate_all();
 
             devices.retain(|device| {
-                let Ok(event_bits) = device.handler.event_bits() else {
-                    return true;
-                };
-
-                event_bits.get(EventKind::Key)
+                (device.is_evdev() || device.is_legacy_mouse())
+                    && device.capabilities.events.get(EventKind::Key)
             });
 
             println!("Select input device: ");
             for device in devices.iter().enumerate() {
-                println!("\t{}: Device: {}", device.0, device.1.name);
+                println!("\t{}: Device: {}", device.0, device.1.display_name());
             }
 
             let num = choose_usize("", None);
@@ -133,12 +272,12 @@ impl InputDevice {
             }
 
             if choose_yes(
-                format!("Device selected: {}, Is Ok", devices[num].name),
+                format!("Device selected: {}, Is Ok", devices[num].display_name()),
                 true,
             ) {
                 let device = devices.remove(num);
 
-                return device;
+                return InputDevice::dev_open(device.path).unwrap();
             }
         }
     }
@@ -147,6 +286,63 @@ impl InputDevice {
         self.handler.read(events)
     }
 
+    /// The name reported by the device, without the `-eventN`
+    pub fn device_name(&self) -> String {
+        let name_bytes = self.handler.device_name().unwrap_or(vec![]);
+        String::from_utf8_lossy(&name_bytes)
+            .trim_end_matches('\0')
+            .to_owned()
+    }
+
+    pub fn write(&self, events: &[input_event]) -> io::Result<usize> {
+        self.handler.write(events)
+    }
+
+    pub fn identity(&self) -> DeviceIdentity {
+        let string = |bytes: io::Result<Vec<u8>>| {
+            String::from_utf8_lossy(&bytes.unwrap_or_default())
+                .trim_end_matches('\0')
+                .to_owned()
+        };
+        let id = self.handler.device_id().unwrap_or_default();
+        DeviceIdentity {
+            name: self.device_name(),
+            bustype: id.bustype,
+            vendor: id.vendor,
+            product: id.product,
+            uniq: string(self.handler.unique_id()),
+        }
+    }
+
+    /// The keys that are pressed now, `EVIOCGKEY`
+    pub fn key_state(&self) -> io::Result<Bitmask<Key>> {
+        let mut keys = Bitmask::default();
+        self.handler.key_state(&mut keys).map(|_| keys)
+    }
+
+    pub fn capabilities(&self) -> Capabilities {
+        let handler = &self.handler;
+        Capabilities {
+            events: handler.event_bits().unwrap_or_default(),
+            keys: handler.key_bits().unwrap_or_default(),
+            relative: handler.relative_bits().unwrap_or_default(),
+            absolute: handler.absolute_bits().unwrap_or_default(),
+            properties: handler.device_properties().unwrap_or_default(),
+        }
+    }
+
+    pub fn device_type(&self) -> DeviceType {
+        DeviceType::from(&self.capabilities())
+    }
+
+    /// The suggested binds for the left, middle and right autoclicker, that the device has
+    pub fn suggested_binds(&self) -> [Option<Key>; 3] {
+        let capabilities = self.capabilities();
+        DeviceType::from(&capabilities)
+            .suggested_binds()
+            .map(|key| key.filter(|key| capabilities.keys.get(*key)))
+    }
+
     pub fn grab(&self, grab: bool) -> io::Result<()> {
         self.handler.grab(grab)
     }
@@ -169,23 +365,225 @@ impl InputDevice {
     }
 }
 
+/// A device node, described by sysfs, so it can be listed and matched without opening it
+pub struct DeviceInfo {
+    pub path: PathBuf,
+    pub filename: String,
+    /// The name reported by the device, without the `-eventN`
+    pub name: String,
+    pub phys: String,
+    pub uniq: String,
+    pub id: InputId,
+    pub capabilities: Capabilities,
+    /// The `/dev/input/by-id` and `/dev/input/by-path` links to the node
+    pub links: Vec<PathBuf>,
+}
+
+impl DeviceInfo {
+    /// The evdev nodes, `eventN`, event2 before event10
+    ///
+    /// The legacy `mouseN` and `jsN` nodes have the name, id and capabilities of their `eventN`,
+    /// so they would match every query twice
+    pub fn enumerate() -> Vec<DeviceInfo> {
+        let mut devices = Self::enumerate_all();
+        devices.retain(DeviceInfo::is_evdev);
+        devices
+    }
+
+    /// Every node of `/sys/class/input` that is in `/dev/input`, also the legacy `mouseN` and `jsN`
+    pub fn enumerate_all() -> Vec<DeviceInfo> {
+        let Ok(dir) = fs::read_dir("/sys/class/input") else {
+            return Vec::new();
+        };
+        let mut links = Vec::new();
+        for dir in ["/dev/input/by-id", "/dev/input/by-path"] {
+            let Ok(dir) = fs::read_dir(dir) else {
+                continue;
+            };
+            for entry in dir.filter_map(|res| res.ok()) {
+                if let Ok(target) = fs::canonicalize(entry.path()) {
+                    links.push((target, entry.path()));
+                }
+            }
+        }
+
+        let mut devices = dir
+            .filter_map(|res| res.ok())
+            .filter_map(|entry| {
+                let filename = entry.file_name().into_string().ok()?;
+                // The inputN are the devices, the other entries are their nodes
+                if filename.starts_with("input") || filename == "mice" {
+                    return None;
+                }
+                let mut device = Self::from_sysfs(filename)?;
+                device.links = links
+                    .iter()
+                    .filter(|(target, _)| *target == device.path)
+                    .map(|(_, link)| link.clone())
+                    .collect();
+                Some(device)
+            })
+            .collect::<Vec<_>>();
+
+        devices.sort_by_key(|device| {
+            let digits = device
+                .filename
+                .trim_start_matches(|c: char| !c.is_ascii_digit());
+            let number = digits.parse::<u32>().unwrap_or(0);
+            (device.filename.trim_end_matches(digits).to_owned(), number)
+        });
+        devices
+    }
+
+    fn from_sysfs(filename: String) -> Option<DeviceInfo> {
+        let path = PathBuf::from("/dev/input").join(&filename);
+        if !path.exists() {
+            return None;
+        }
+        let sysfs = PathBuf::from("/sys/class/input")
+            .join(&filename)
+            .join("device");
+        let read = |file: &str| {
+            fs::read_to_string(sysfs.join(file))
+                .map(|value| value.trim_end().to_owned())
+                .unwrap_or_default()
+        };
+        let hex = |file: &str| u16::from_str_radix(&read(file), 16).unwrap_or(0);
+
+        Some(DeviceInfo {
+            name: read("name"),
+            phys: read("phys"),
+            uniq: read("uniq"),
+            id: InputId {
+                bustype: hex("id/bustype"),
+                vendor: hex("id/vendor"),
+                product: hex("id/product"),
+                version: hex("id/version"),
+            },
+            capabilities: Capabilities {
+                events: sysfs_bits(&read("capabilities/ev")),
+                keys: sysfs_bits(&read("capabilities/key")),
+                relative: sysfs_bits(&read("capabilities/rel")),
+                absolute: sysfs_bits(&read("capabilities/abs")),
+                properties: sysfs_bits(&read("properties")),
+            },
+            links: Vec::new(),
+            path,
+            filename,
+        })
+    }
+
+    /// Every node of the device of `evdev_path`, the `eventN` and its legacy `mouseN` and `jsN`
+    pub fn nodes(evdev_path: &Path) -> Vec<PathBuf> {
+        let Some(filename) = evdev_path.file_name() else {
+            return Vec::new();
+        };
+        let Ok(dir) = fs::read_dir(
+            PathBuf::from("/sys/class/input")
+                .join(filename)
+                .join("device"),
+        ) else {
+            return vec![evdev_path.to_owned()];
+        };
+        let mut nodes = dir
+            .filter_map(|res| res.ok())
+            .map(|entry| entry.file_name())
+            .filter(|name| {
+                let name = name.to_string_lossy();
+                ["event", "mouse", "js"]
+                    .iter()
+                    .any(|prefix| name.starts_with(prefix))
+            })
+            .map(|name| PathBuf::from("/dev/input").join(name))
+            .collect::<Vec<_>>();
+        if !nodes.iter().any(|node| node == evdev_path) {
+            nodes.push(evdev_path.to_owned());
+        }
+        nodes
+    }
+
+    pub fn is_evdev(&self) -> bool {
+        self.filename.starts_with("event")
+    }
+
+    /// A `mouseN`, read by `run-legacy`
+    pub fn is_legacy_mouse(&self) -> bool {
+        self.filename.starts_with("mouse")
+    }
+
+    /// Like `InputDevice::name`, `name-eventN`
+    pub fn display_name(&self) -> String {
+        format!("{}-{}", self.name, self.filename)
+    }
+
+    pub fn device_type(&self) -> DeviceType {
+        DeviceType::from(&self.capabilities)
+    }
+}
+
+/// Parses a bitmap of sysfs, hex words of `unsigned long` with the most significant first
+fn sysfs_bits<T: BitmaskTrait>(value: &str) -> Bitmask<T> {
+    let mut bits = Bitmask::default();
+    let bytes: &mut [u8] = bits.as_mut();
+    let word_bits = nix::libc::c_ulong::BITS as usize;
+    for (index, word) in value.split_whitespace().rev().enumerate() {
+        let word = u64::from_str_radix(word, 16).unwrap_or(0);
+        for bit in (0..word_bits).filter(|bit| word >> bit & 1 == 1) {
+            let bit = index * word_bits + bit;
+            if let Some(byte) = bytes.get_mut(bit / 8) {
+                *byte |= 1 << (bit % 8);
+            }
+        }
+    }
+    bits
+}
+
 pub struct OutputDevice {
     pub name: String,
     pub path: PathBuf,
     pub filename: String,
     pub handler: UInputHandle<File>,
+    /// The absolute axes that will be set up at creation
+    pub abs: Vec<AbsoluteInfoSetup>,
+    /// The autorepeat delay and period, that will be set after creation
+    pub repeat: Option<repeat_settings>,
+    pub id: InputId,
+    pub phys: Option<CString>,
+    /// Prints every written event, with --debug
+    pub trace: bool,
+    /// Reports `REL_WHEEL_HI_RES` or `REL_HWHEEL_HI_RES`, the compositor ignores the low-resolution wheel events then
+    pub hi_res_wheel: bool,
+    /// `/dev/uinput` was opened with read access, so the LED and force-feedback events can be forwarded
+    pub readable: bool,
 }
 
 impl OutputDevice {
     pub fn uinput_open(path: PathBuf, name: &str) -> Result<Self, String> {
-        let file = match fs::OpenOptions::new().write(true).open(&path) {
-            Ok(file) => file,
+        // Read is needed only for the LED and force-feedback events sent to the grabbed devices,
+        // without it the clicks still work
+        let opened = fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&path)
+            .map(|file| (file, true))
+            .or_else(|err| {
+                if err.raw_os_error() == Some(nix::libc::EACCES) {
+                    fs::OpenOptions::new()
+                        .write(true)
+                        .open(&path)
+                        .map(|file| (file, false))
+                } else {
+                    Err(err)
+                }
+            });
+        let (file, readable) = match opened {
+            Ok(opened) => opened,
             Err(err) => {
                 println!("Error: {}", err);
                 match err.raw_os_error() {
                     Some(13) => {
                         println!(
-                            "Not having permissions to create device, add the input group to your use then logout or run as root!"
+                            "Not having permissions to create device, run `sudo theclicker setup udev` then login again, or run as root!"
                         );
                     }
                     Some(19) => {
@@ -198,20 +596,76 @@ impl OutputDevice {
                         println!("Not having access to create device, try as root!");
                     }
                 }
+                println!("Run `theclicker doctor` to see what is missing");
                 exit(1);
             }
         };
 
         let handler = UInputHandle::new(file);
+        let name = uinput_name(name);
 
         Ok(Self {
             path,
             handler,
-            name: name.to_string(),
-            filename: name.to_string(),
+            filename: name.clone(),
+            name,
+            abs: Vec::new(),
+            repeat: None,
+            id: InputId {
+                bustype: BUS_USB,
+                vendor: VENDOR,
+                product: PRODUCT,
+                version: VERSION,
+            },
+            phys: None,
+            trace: false,
+            hi_res_wheel: false,
+            readable,
         })
     }
 
+    /// Takes the name, ids and phys of the device
+    pub fn mirror_identity(&mut self, from: &InputDevice) {
+        self.name = uinput_name(&from.device_name());
+        self.filename = self.name.clone();
+        if let Ok(id) = from.handler.device_id() {
+            self.id = id;
+        }
+        if let Ok(mut phys) = from.handler.physical_location() {
+            // The kernel includes the null terminator
+            while phys.last() == Some(&0) {
+                phys.pop();
+            }
+            self.phys = CString::new(phys).ok();
+        }
+    }
+
+    /// Overrides only what is set in the identity
+    pub fn set_identity(&mut self, identity: &Identity) {
+        if let Some(name) = &identity.name {
+            self.name = name.clone();
+            self.filename = name.clone();
+        }
+        if let Some(bus) = identity.bus {
+            self.id.bustype = bus.bustype();
+        }
+        if let Some(vendor) = identity.vendor {
+            self.id.vendor = vendor;
+        }
+        if let Some(product) = identity.product {
+            self.id.product = product;
+        }
+        if let Some(version) = identity.device_version {
+            self.id.version = version;
+        }
+        if let Some(phys) = &identity.phys {
+            match CString::new(phys.as_str()) {
+                Ok(phys) => self.phys = Some(phys),
+                Err(err) => eprintln!("Invalid phys {phys:?}: {err}"),
+            }
+        }
+    }
+
     pub fn add_mouse_attributes(&self, legacy: bool) {
         self.handler.set_evbit(EventKind::Key).unwrap();
         self.handler.set_evbit(EventKind::Synchronize).unwrap();
@@ -223,8 +677,99 @@ impl OutputDevice {
         self.handler.set_keybit(Key::ButtonRight).unwrap();
     }
 
+    /// With `hi_res` the device will also report `REL_WHEEL_HI_RES` and `REL_HWHEEL_HI_RES`,
+    /// after that the compositor will ignore the low-resolution wheel events.
+    pub fn add_scroll_attributes(&mut self, hi_res: bool) {
+        self.handler.set_evbit(EventKind::Relative).unwrap();
+        self.handler.set_evbit(EventKind::Synchronize).unwrap();
+
+        self.handler.set_relbit(RelativeAxis::Wheel).unwrap();
+        self.handler
+            .set_relbit(RelativeAxis::HorizontalWheel)
+            .unwrap();
+        if hi_res {
+            self.handler.set_relbit(RelativeAxis::WheelHiRes).unwrap();
+            self.handler
+                .set_relbit(RelativeAxis::HorizontalWheelHiRes)
+                .unwrap();
+            self.hi_res_wheel = true;
+        }
+    }
+
+    pub fn add_motion_attributes(&self) {
+        self.handler.set_evbit(EventKind::Relative).unwrap();
+        self.handler.set_evbit(EventKind::Synchronize).unwrap();
+
+        self.handler.set_relbit(RelativeAxis::X).unwrap();
+        self.handler.set_relbit(RelativeAxis::Y).unwrap();
+    }
+
+    pub fn add_key_attributes(&self, key: Key) {
+        self.handler.set_evbit(EventKind::Key).unwrap();
+        self.handler.set_evbit(EventKind::Synchronize).unwrap();
+
+        self.handler.set_keybit(key).unwrap();
+    }
+
+    /// A direct absolute device, the position is the same on every output, ignoring acceleration
+    pub fn add_tablet_attributes(&mut self) {
+        self.handler.set_evbit(EventKind::Key).unwrap();
+        self.handler.set_evbit(EventKind::Absolute).unwrap();
+        self.handler.set_evbit(EventKind::Synchronize).unwrap();
+        self.handler.set_propbit(InputProperty::Direct).unwrap();
+
+        for key in [
+            Key::ButtonTouch,
+            Key::ButtonLeft,
+            Key::ButtonMiddle,
+            Key::ButtonRight,
+        ] {
+            self.handler.set_keybit(key).unwrap();
+        }
+
+        for axis in [AbsoluteAxis::X, AbsoluteAxis::Y] {
+            self.handler.set_absbit(axis).unwrap();
+            self.abs.push(AbsoluteInfoSetup {
+                axis,
+                info: AbsoluteInfo {
+                    maximum: TABLET_MAX,
+                    ..Default::default()
+                },
+            });
+        }
+    }
+
+    /// A direct multitouch device, using the type B protocol
+    pub fn add_touchscreen_attributes(&mut self) {
+        self.handler.set_evbit(EventKind::Key).unwrap();
+        self.handler.set_evbit(EventKind::Absolute).unwrap();
+        self.handler.set_evbit(EventKind::Synchronize).unwrap();
+        self.handler.set_propbit(InputProperty::Direct).unwrap();
+
+        self.handler.set_keybit(Key::ButtonTouch).unwrap();
+
+        for (axis, minimum, maximum) in [
+            (AbsoluteAxis::X, 0, TABLET_MAX),
+            (AbsoluteAxis::Y, 0, TABLET_MAX),
+            (AbsoluteAxis::MultitouchSlot, 0, TOUCH_SLOTS as i32 - 1),
+            (AbsoluteAxis::MultitouchTrackingId, 0, u16::MAX as i32),
+            (AbsoluteAxis::MultitouchPositionX, 0, TABLET_MAX),
+            (AbsoluteAxis::MultitouchPositionY, 0, TABLET_MAX),
+        ] {
+            self.handler.set_absbit(axis).unwrap();
+            self.abs.push(AbsoluteInfoSetup {
+                axis,
+                info: AbsoluteInfo {
+                    minimum,
+                    maximum,
+                    ..Default::default()
+                },
+            });
+        }
+    }
+
     /// Only copis attributes from DevInput to UInput
-    pub fn copy_attributes(&self, debug: bool, from: &InputDevice) {
+    pub fn copy_attributes(&mut self, debug: bool, from: &InputDevice) {
         let to = &self.handler;
         let from = &from.handler;
 
@@ -244,17 +789,38 @@ impl OutputDevice {
             for bit in bits.iter() {
                 to.set_relbit(bit).unwrap();
             }
+            self.hi_res_wheel |=
+                bits.get(RelativeAxis::WheelHiRes) || bits.get(RelativeAxis::HorizontalWheelHiRes);
         }
 
-        // FIX: - TheClicker: kernel bug: device has min == max on ABS_VOLUME
-        // if let Ok(bits) = from.absolute_bits() {
-        //     if debug {
-        //         println!("Copy absolute_bits: {bits:?}")
-        //     }
-        //     for bit in bits.iter() {
-        //         to.set_absbit(bit).unwrap();
-        //     }
-        // }
+        if let Ok(bits) = from.absolute_bits() {
+            if debug {
+                println!("Copy absolute_bits: {bits:?}")
+            }
+            let mut abs = Vec::new();
+            for axis in bits.iter() {
+                let info = match from.absolute_info(axis) {
+                    Ok(info) => info,
+                    Err(err) => {
+                        eprintln!(
+                            "\x1B[1;33mSkipping {axis:?}, cannot read absinfo: {err}\x1B[22;39m"
+                        );
+                        continue;
+                    }
+                };
+                let Some(info) = repair_absolute_info(axis, info) else {
+                    continue;
+                };
+                if debug {
+                    println!("Copy absolute_info {axis:?}: {info:?}")
+                }
+                to.set_absbit(axis).unwrap();
+                abs.push(AbsoluteInfoSetup { axis, info });
+            }
+            self.abs
+                .retain(|setup| !abs.iter().any(|copy| copy.axis == setup.axis));
+            self.abs.extend(abs);
+        }
 
         if let Ok(bits) = from.misc_bits() {
             if debug {
@@ -273,28 +839,212 @@ impl OutputDevice {
                 to.set_keybit(bit).unwrap();
             }
         }
+
+        if let Ok(bits) = from.device_properties() {
+            if debug {
+                println!("Copy device_properties: {bits:?}")
+            }
+            for bit in bits.iter() {
+                to.set_propbit(bit).unwrap();
+            }
+        }
+
+        if let Ok(bits) = from.switch_bits() {
+            if debug {
+                println!("Copy switch_bits: {bits:?}")
+            }
+            for bit in bits.iter() {
+                to.set_swbit(bit).unwrap();
+            }
+        }
+
+        if let Ok(bits) = from.led_bits() {
+            if debug {
+                println!("Copy led_bits: {bits:?}")
+            }
+            for bit in bits.iter() {
+                to.set_ledbit(bit).unwrap();
+            }
+        }
+
+        if let Ok(bits) = from.sound_bits() {
+            if debug {
+                println!("Copy sound_bits: {bits:?}")
+            }
+            for bit in bits.iter() {
+                to.set_sndbit(bit).unwrap();
+            }
+        }
+
+        if let Ok(bits) = from.force_feedback_bits() {
+            if debug {
+                println!("Copy force_feedback_bits: {bits:?}")
+            }
+            for bit in bits.iter() {
+                to.set_ffbit(bit).unwrap();
+            }
+        }
+
+        if let Ok(bits) = from.event_bits()
+            && bits.get(EventKind::Autorepeat)
+            && let Ok(repeat) = from.repeat_settings()
+        {
+            if debug {
+                println!(
+                    "Copy repeat_settings: delay: {}ms period: {}ms",
+                    repeat.delay, repeat.period
+                )
+            }
+            self.repeat = Some(repeat);
+        }
     }
 
     pub fn create(&self) {
+        if let Some(phys) = &self.phys {
+            self.handler.set_phys(phys).unwrap();
+        }
+
         self.handler
             .create(
-                &InputId {
-                    bustype: BUS_USB,
-                    vendor: VENDOR,
-                    product: PRODUCT,
-                    version: VERSION,
-                },
+                &self.id,
                 self.name.as_bytes(),
                 input_linux::sys::FF_MAX_EFFECTS as u32,
-                &[],
+                &self.abs,
             )
             .unwrap();
+
+        // The kernel sets the default autorepeat, that can be changed only with events
+        if let Some(repeat) = self.repeat {
+            let time = get_current_time();
+            let events = [
+                (AutorepeatKind::Delay, repeat.delay),
+                (AutorepeatKind::Period, repeat.period),
+            ]
+            .map(|(kind, value)| {
+                *InputEvent::from(AutorepeatEvent::new(time, kind, value as i32)).as_raw()
+            });
+            self.write(&events)
+                .unwrap_or_else(|err| panic!("Cannot set autorepeat: {err} {events:?}"));
+        }
     }
 
     pub fn write(&self, events: &[input_event]) -> io::Result<usize> {
+        if self.trace {
+            for event in events {
+                println!("Output {}: {}", self.name, Decoded(event));
+            }
+        }
         self.handler.write(events)
     }
 
+    /// Forwards the LED and force-feedback events, that the compositor or the games
+    /// send to the virtual device, to the grabbed devices.
+    /// `effects` maps the effect ids of the virtual device, to the effect ids of the grabbed device.
+    /// Only a failed read or `UI_FF_*` ioctl is an error, the failed writes are printed
+    pub fn forward_feedback(
+        &self,
+        to: &[&InputDevice],
+        debug: bool,
+        effects: &mut HashMap<i16, i16>,
+    ) -> io::Result<()> {
+        let mut events: [input_event; 16] = unsafe { std::mem::zeroed() };
+
+        let len = self.handler.read(&mut events)?;
+        // The LEDs are set on every device, the effects are played by the first one that has force-feedback.
+        // Without a connected device the requests are still answered, or the game waits for the uinput timeout
+        let ff = to
+            .iter()
+            .find(|device| {
+                device
+                    .handler
+                    .event_bits()
+                    .is_ok_and(|bits| bits.get(EventKind::ForceFeedback))
+            })
+            .or(to.first());
+        for event in &events[..len] {
+            if debug {
+                println!("Feedback: {}", Decoded(event));
+            }
+
+            let syn = *InputEvent::from(SynchronizeEvent::report(get_current_time())).as_raw();
+
+            match event.type_ as i32 {
+                // A failed write only loses this event, the requests after it still need an answer
+                sys::EV_LED => {
+                    for to in to {
+                        if let Err(err) = to.write(&[*event, syn]) {
+                            eprintln!(
+                                "\x1B[1;31mCannot forward {} to {}: {err}\x1B[22;39m",
+                                Decoded(event),
+                                to.name
+                            );
+                        }
+                    }
+                }
+                sys::EV_FF => {
+                    let Some(ff) = ff else {
+                        continue;
+                    };
+                    let mut event = *event;
+                    // FF_GAIN and FF_AUTOCENTER are not effects
+                    if event.code < sys::FF_GAIN {
+                        let Some(id) = effects.get(&(event.code as i16)) else {
+                            continue;
+                        };
+                        event.code = *id as u16;
+                    }
+                    if let Err(err) = ff.write(&[event, syn]) {
+                        eprintln!(
+                            "\x1B[1;31mCannot forward {} to {}: {err}\x1B[22;39m",
+                            Decoded(&event),
+                            ff.name
+                        );
+                    }
+                }
+                sys::EV_UINPUT => match event.code as i32 {
+                    sys::UI_FF_UPLOAD => {
+                        let mut upload: sys::uinput_ff_upload = unsafe { std::mem::zeroed() };
+                        upload.request_id = event.value as u32;
+                        self.handler.ff_upload_begin(&mut upload)?;
+
+                        let mut effect = upload.effect;
+                        effect.id = effects.get(&upload.effect.id).copied().unwrap_or(-1);
+                        upload.retval =
+                            match ff.map(|ff| ff.handler.send_force_feedback(&mut effect)) {
+                                Some(Ok(())) => {
+                                    effects.insert(upload.effect.id, effect.id);
+                                    0
+                                }
+                                Some(Err(err)) => -err.raw_os_error().unwrap_or(nix::libc::EINVAL),
+                                None => -nix::libc::ENODEV,
+                            };
+
+                        self.handler.ff_upload_end(&upload)?;
+                    }
+                    sys::UI_FF_ERASE => {
+                        let mut erase: sys::uinput_ff_erase = unsafe { std::mem::zeroed() };
+                        erase.request_id = event.value as u32;
+                        self.handler.ff_erase_begin(&mut erase)?;
+
+                        erase.retval = match (effects.remove(&(erase.effect_id as i16)), ff) {
+                            (Some(id), Some(ff)) => match ff.handler.erase_force_feedback(id) {
+                                Ok(()) => 0,
+                                Err(err) => -err.raw_os_error().unwrap_or(nix::libc::EINVAL),
+                            },
+                            (Some(_), None) => -nix::libc::ENODEV,
+                            (None, _) => 0,
+                        };
+
+                        self.handler.ff_erase_end(&erase)?;
+                    }
+                    _ => {}
+                },
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
     pub fn send_key(&self, key: Key, state: KeyState) {
         let events: [input_event; 2] = [
             InputEvent::from(KeyEvent::new(get_current_time(), key, state))
@@ -305,10 +1055,208 @@ impl OutputDevice {
                 .to_owned(),
         ];
         self.write(&events)
-            .expect("Cannot send key event: {events:?}");
+            .unwrap_or_else(|err| panic!("Cannot send key event: {err} {events:?}"));
+    }
+
+    /// Presses or releases `keys` on the tablet, touching the surface at `point` when pressed
+    pub fn send_tablet_keys(&self, point: Point, keys: &[Key], state: KeyState) {
+        let time = get_current_time();
+        let mut events: Vec<input_event> = Vec::with_capacity(keys.len() + 4);
+        if state == KeyState::PRESSED {
+            events.extend(absolute_position(
+                time,
+                AbsoluteAxis::X,
+                AbsoluteAxis::Y,
+                point,
+            ));
+        }
+        for key in std::iter::once(&Key::ButtonTouch).chain(keys) {
+            events.push(*InputEvent::from(KeyEvent::new(time, *key, state)).as_raw());
+        }
+        events.push(*InputEvent::from(SynchronizeEvent::report(time)).as_raw());
+
+        self.write(&events)
+            .unwrap_or_else(|err| panic!("Cannot send tablet event: {err} {events:?}"));
+    }
+
+    pub fn send_tablet_position(&self, point: Point) {
+        let time = get_current_time();
+        let mut events = absolute_position(time, AbsoluteAxis::X, AbsoluteAxis::Y, point).to_vec();
+        events.push(*InputEvent::from(SynchronizeEvent::report(time)).as_raw());
+
+        self.write(&events)
+            .unwrap_or_else(|err| panic!("Cannot send tablet event: {err} {events:?}"));
+    }
+
+    /// Sends a frame for every finger, the first finger is in the slot 0
+    pub fn send_touch(&self, fingers: &[Point], state: TouchState) {
+        let time = get_current_time();
+        let abs = |axis, value| *InputEvent::from(AbsoluteEvent::new(time, axis, value)).as_raw();
+
+        let mut events: Vec<input_event> = Vec::with_capacity(fingers.len() * 4 + 4);
+        for (slot, finger) in fingers.iter().enumerate() {
+            events.push(abs(AbsoluteAxis::MultitouchSlot, slot as i32));
+            match state {
+                TouchState::Down(tracking_id) => {
+                    events.push(abs(
+                        AbsoluteAxis::MultitouchTrackingId,
+                        (tracking_id + slot as i32) & u16::MAX as i32,
+                    ));
+                }
+                TouchState::Up => {
+                    events.push(abs(AbsoluteAxis::MultitouchTrackingId, -1));
+                    continue;
+                }
+                TouchState::Move => {}
+            }
+            events.extend(absolute_position(
+                time,
+                AbsoluteAxis::MultitouchPositionX,
+                AbsoluteAxis::MultitouchPositionY,
+                *finger,
+            ));
+        }
+
+        match state {
+            TouchState::Down(_) => events.push(
+                *InputEvent::from(KeyEvent::new(time, Key::ButtonTouch, KeyState::PRESSED))
+                    .as_raw(),
+            ),
+            TouchState::Up => events.push(
+                *InputEvent::from(KeyEvent::new(time, Key::ButtonTouch, KeyState::RELEASED))
+                    .as_raw(),
+            ),
+            TouchState::Move => {}
+        }
+        // The single touch emulation, follows the first finger
+        if !matches!(state, TouchState::Up)
+            && let Some(first) = fingers.first()
+        {
+            events.extend(absolute_position(
+                time,
+                AbsoluteAxis::X,
+                AbsoluteAxis::Y,
+                *first,
+            ));
+        }
+        events.push(*InputEvent::from(SynchronizeEvent::report(time)).as_raw());
+
+        self.write(&events)
+            .unwrap_or_else(|err| panic!("Cannot send touch event: {err} {events:?}"));
+    }
+
+    pub fn send_motion(&self, dx: i32, dy: i32) {
+        let time = get_current_time();
+        let mut events: Vec<input_event> = Vec::with_capacity(3);
+        if dx != 0 {
+            events.push(*InputEvent::from(RelativeEvent::new(time, RelativeAxis::X, dx)).as_raw());
+        }
+        if dy != 0 {
+            events.push(*InputEvent::from(RelativeEvent::new(time, RelativeAxis::Y, dy)).as_raw());
+        }
+        if events.is_empty() {
+            return;
+        }
+        events.push(*InputEvent::from(SynchronizeEvent::report(time)).as_raw());
+
+        self.write(&events)
+            .unwrap_or_else(|err| panic!("Cannot send motion event: {err} {events:?}"));
+    }
+
+    /// Scrolls `detents` wheel detents, and when `hi_res` is set, `hi_res` 1/120 of a detent.
+    /// A device with the hi-res wheels always gets the hi-res event, or the scroll would be ignored
+    pub fn send_scroll(&self, direction: ScrollDirection, detents: i32, hi_res: Option<i32>) {
+        let hi_res =
+            hi_res.or((self.hi_res_wheel && detents != 0).then_some(detents * WHEEL_HI_RES_DETENT));
+        let (axis, axis_hi_res, sign) = match direction {
+            ScrollDirection::Up => (RelativeAxis::Wheel, RelativeAxis::WheelHiRes, 1),
+            ScrollDirection::Down => (RelativeAxis::Wheel, RelativeAxis::WheelHiRes, -1),
+            ScrollDirection::Left => (
+                RelativeAxis::HorizontalWheel,
+                RelativeAxis::HorizontalWheelHiRes,
+                -1,
+            ),
+            ScrollDirection::Right => (
+                RelativeAxis::HorizontalWheel,
+                RelativeAxis::HorizontalWheelHiRes,
+                1,
+            ),
+        };
+
+        let time = get_current_time();
+        let mut events: Vec<input_event> = Vec::with_capacity(3);
+        if detents != 0 {
+            events.push(*InputEvent::from(RelativeEvent::new(time, axis, detents * sign)).as_raw());
+        }
+        if let Some(value) = hi_res {
+            events.push(
+                *InputEvent::from(RelativeEvent::new(time, axis_hi_res, value * sign)).as_raw(),
+            );
+        }
+        if events.is_empty() {
+            return;
+        }
+        events.push(*InputEvent::from(SynchronizeEvent::report(time)).as_raw());
+
+        self.write(&events)
+            .unwrap_or_else(|err| panic!("Cannot send scroll event: {err} {events:?}"));
     }
 }
 
+/// The name cut to `MAX_NAME_LEN` bytes, the kernel names of the devices can be longer
+fn uinput_name(name: &str) -> String {
+    let mut len = name.len().min(MAX_NAME_LEN);
+    while !name.is_char_boundary(len) {
+        len -= 1;
+    }
+    name[..len].to_owned()
+}
+
+/// The kernel refuses to create a device with an invalid axis, some devices report
+/// one anyway (min == max on ABS_VOLUME), so it is repaired when possible or skipped.
+fn repair_absolute_info(axis: AbsoluteAxis, mut info: AbsoluteInfo) -> Option<AbsoluteInfo> {
+    if info.maximum < info.minimum {
+        eprintln!(
+            "\x1B[1;33mSkipping {axis:?}, the min {} is greater than the max {}\x1B[22;39m",
+            info.minimum, info.maximum
+        );
+        return None;
+    }
+
+    if info.maximum == info.minimum {
+        eprintln!(
+            "\x1B[1;33mRepairing {axis:?}, the min is equal to the max {}\x1B[22;39m",
+            info.maximum
+        );
+        info.maximum = info.minimum.saturating_add(1);
+    }
+
+    let range = info.maximum.saturating_sub(info.minimum);
+    if info.flat > range || info.fuzz > range {
+        eprintln!(
+            "\x1B[1;33mRepairing {axis:?}, the flat {} or fuzz {} is greater than the range {range}\x1B[22;39m",
+            info.flat, info.fuzz
+        );
+        info.flat = info.flat.min(range);
+        info.fuzz = info.fuzz.min(range);
+    }
+
+    info.value = info.value.clamp(info.minimum, info.maximum);
+    Some(info)
+}
+
+fn absolute_position(
+    time: EventTime,
+    x: AbsoluteAxis,
+    y: AbsoluteAxis,
+    point: Point,
+) -> [input_event; 2] {
+    [(x, point.x), (y, point.y)].map(|(axis, value)| {
+        let value = (value * TABLET_MAX as f64).round() as i32;
+        *InputEvent::from(AbsoluteEvent::new(time, axis, value)).as_raw()
+    })
+}
+
 pub fn get_current_time() -> EventTime {
     let time = SystemTime::now()
         .duration_since(SystemTime::UNIX_EPOCH)
@@ -316,3 +1264,124 @@ pub fn get_current_time() -> EventTime {
 
     EventTime::new(time.as_secs() as i64, time.subsec_micros() as i64)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn sysfs_bits_of_a_mouse() {
+        let events: Bitmask<EventKind> = sysfs_bits("17");
+        assert_eq!(
+            events.iter().collect::<Vec<_>>(),
+            [
+                EventKind::Synchronize,
+                EventKind::Key,
+                EventKind::Relative,
+                EventKind::Misc
+            ]
+        );
+
+        let relative: Bitmask<RelativeAxis> = sysfs_bits("903");
+        assert_eq!(
+            relative.iter().collect::<Vec<_>>(),
+            [
+                RelativeAxis::X,
+                RelativeAxis::Y,
+                RelativeAxis::Wheel,
+                RelativeAxis::WheelHiRes
+            ]
+        );
+
+        // The most significant word first, BTN_LEFT is 0x110, in the fifth word
+        let keys: Bitmask<Key> = sysfs_bits("1f0000 0 0 0 0");
+        assert_eq!(
+            keys.iter().collect::<Vec<_>>(),
+            [
+                Key::ButtonLeft,
+                Key::ButtonRight,
+                Key::ButtonMiddle,
+                Key::ButtonSide,
+                Key::ButtonExtra
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn sysfs_bits_of_a_keyboard() {
+        let keys: Bitmask<Key> =
+            sysfs_bits("1000000000007 ff9f207ac14057ff febeffdfffefffff fffffffffffffffe");
+        assert!(!keys.get(Key::Reserved));
+        assert!(keys.get(Key::Esc));
+        assert!(keys.get(Key::A));
+        assert!(keys.get(Key::Space));
+        // The first bit of the second word, KEY_F6 is 64
+        assert!(keys.get(Key::F6));
+        assert!(!keys.get(Key::ButtonLeft));
+        // 0x1000000000007 in the most significant word, bits 192, 193, 194 and 240
+        let high = keys
+            .iter()
+            .filter(|key| *key as u16 >= 192)
+            .map(|key| key as u16)
+            .collect::<Vec<_>>();
+        assert_eq!(high, [192, 193, 194, 240]);
+    }
+
+    #[test]
+    fn sysfs_bits_ignores_garbage() {
+        let keys: Bitmask<Key> = sysfs_bits("");
+        assert_eq!(keys.iter().count(), 0);
+        let keys: Bitmask<Key> = sysfs_bits("zz 1");
+        assert_eq!(keys.iter().collect::<Vec<_>>(), [Key::Reserved]);
+        // More words than the bitmask has are dropped
+        let events: Bitmask<EventKind> = sysfs_bits("ffffffffffffffff 0 0 0 1");
+        assert_eq!(events.iter().collect::<Vec<_>>(), [EventKind::Synchronize]);
+    }
+
+    fn info(minimum: i32, maximum: i32, fuzz: i32, flat: i32, value: i32) -> AbsoluteInfo {
+        AbsoluteInfo {
+            value,
+            minimum,
+            maximum,
+            fuzz,
+            flat,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn repair_absolute_info_keeps_valid_axes() {
+        let valid = info(0, 4095, 4, 8, 100);
+        assert_eq!(repair_absolute_info(AbsoluteAxis::X, valid), Some(valid));
+    }
+
+    #[test]
+    fn repair_absolute_info_repairs_or_skips() {
+        assert_eq!(
+            repair_absolute_info(AbsoluteAxis::Volume, info(0, 0, 0, 0, 0)),
+            Some(info(0, 1, 0, 0, 0))
+        );
+        assert_eq!(
+            repair_absolute_info(AbsoluteAxis::X, info(0, 10, 20, 30, 0)),
+            Some(info(0, 10, 10, 10, 0))
+        );
+        assert_eq!(
+            repair_absolute_info(AbsoluteAxis::X, info(-10, 10, 0, 0, 50)),
+            Some(info(-10, 10, 0, 0, 10))
+        );
+        assert_eq!(
+            repair_absolute_info(AbsoluteAxis::X, info(10, -10, 0, 0, 0)),
+            None
+        );
+    }
+
+    #[test]
+    fn uinput_name_is_cut() {
+        assert_eq!(uinput_name("TheClicker"), "TheClicker");
+        assert_eq!(uinput_name(&"a".repeat(100)), "a".repeat(MAX_NAME_LEN));
+        // Not in the middle of a character
+        assert_eq!(uinput_name(&"é".repeat(50)), "é".repeat(39));
+    }
+}
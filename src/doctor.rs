@@ -0,0 +1,337 @@
+use std::{
+    fs,
+    os::unix::fs::{MetadataExt, PermissionsExt},
+    path::{Path, PathBuf},
+};
+
+use input_linux::EventKind;
+use nix::{
+    errno::Errno,
+    sys::utsname::uname,
+    unistd::{AccessFlags, Gid, Group, Uid, User, access, getgroups},
+};
+
+use crate::{
+    device::{DeviceInfo, InputDevice},
+    query::{DeviceQuery, FindError},
+};
+
+/// Counts the failed checks, every failure is printed with its fix
+#[derive(Default)]
+struct Report {
+    failures: usize,
+}
+
+impl Report {
+    fn ok(&mut self, message: impl std::fmt::Display) {
+        println!("\x1B[1;32m[ OK ]\x1B[0;39m {message}");
+    }
+
+    /// Printed with its fix, without counting as a failure, TheClicker works with fewer features
+    fn warn(&mut self, message: impl std::fmt::Display, fix: impl std::fmt::Display) {
+        println!("\x1B[1;33m[WARN]\x1B[0;39m {message}");
+        println!("       \x1B[1;33mFix:\x1B[0;39m {fix}");
+    }
+
+    fn fail(&mut self, message: impl std::fmt::Display, fix: impl std::fmt::Display) {
+        self.failures += 1;
+        println!("\x1B[1;31m[FAIL]\x1B[0;39m {message}");
+        println!("       \x1B[1;33mFix:\x1B[0;39m {fix}");
+    }
+}
+
+/// Checks what TheClicker needs, for the device of the query or for every device with keys
+pub fn doctor(device_query: Option<String>) {
+    let mut report = Report::default();
+
+    let uid = Uid::current();
+    let user = User::from_uid(uid).ok().flatten();
+    match &user {
+        _ if uid.is_root() => println!("Running as root"),
+        Some(user) => println!("Running as {}", user.name),
+        None => println!("Running as uid {uid}"),
+    }
+    println!();
+
+    check_uinput_module(&mut report);
+    check_uinput_node(&mut report, user.as_ref());
+    check_input_nodes(&mut report, user.as_ref());
+    check_grabs(&mut report, device_query);
+
+    println!();
+    if report.failures == 0 {
+        println!("\x1B[1;32mEverything is ready\x1B[0;39m");
+    } else {
+        println!("\x1B[1;31m{} checks failed\x1B[0;39m", report.failures);
+        std::process::exit(1);
+    }
+}
+
+fn check_uinput_module(report: &mut Report) {
+    if Path::new("/sys/class/misc/uinput").exists() {
+        report.ok("The uinput module is loaded");
+        return;
+    }
+
+    let release = uname()
+        .map(|uname| uname.release().to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let modules = PathBuf::from("/lib/modules").join(&release);
+    // The static /dev/uinput node loads the module when it is opened the first time
+    let autoloaded = fs::read_to_string(modules.join("modules.devname"))
+        .is_ok_and(|devname| devname.lines().any(|line| line.starts_with("uinput ")));
+    if autoloaded || Path::new("/dev/uinput").exists() {
+        report.ok("The uinput module is not loaded yet, it is loaded when /dev/uinput is opened");
+    } else if !modules.exists() {
+        report.fail(
+            format!("The uinput module is not loaded, and there are no modules for the running kernel {release}"),
+            "The kernel was probably updated, reboot",
+        );
+    } else {
+        report.fail(
+            "The uinput module is not loaded",
+            "sudo modprobe uinput, and to load it at boot: echo uinput | sudo tee /etc/modules-load.d/uinput.conf",
+        );
+    }
+}
+
+fn check_uinput_node(report: &mut Report, user: Option<&User>) {
+    let path = Path::new("/dev/uinput");
+    let Ok(metadata) = fs::metadata(path) else {
+        report.fail(
+            "/dev/uinput does not exist",
+            "Load the uinput module, or check that udev is running",
+        );
+        return;
+    };
+
+    let mode = metadata.permissions().mode() & 0o777;
+    let description = format!(
+        "/dev/uinput, mode {mode:o}, owner {}, group {}",
+        user_name(metadata.uid()),
+        group_name(metadata.gid())
+    );
+    // Read is needed to forward the LED and force-feedback events of the grabbed devices
+    if access(path, AccessFlags::R_OK | AccessFlags::W_OK).is_ok() {
+        report.ok(format!("{description} is readable and writable"));
+    } else if access(path, AccessFlags::W_OK).is_ok() {
+        report.warn(
+            format!(
+                "{description} is writable but not readable, the LEDs and force-feedback of grabbed devices will not work"
+            ),
+            group_fix(user, metadata.gid(), mode & 0o060 == 0o060),
+        );
+    } else {
+        report.fail(
+            format!("{description} is not writable"),
+            group_fix(user, metadata.gid(), mode & 0o020 != 0),
+        );
+    }
+}
+
+fn check_input_nodes(report: &mut Report, user: Option<&User>) {
+    let nodes = fs::read_dir("/dev/input")
+        .map(|dir| {
+            dir.filter_map(|res| res.ok())
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.file_name()
+                        .is_some_and(|name| name.to_string_lossy().starts_with("event"))
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    if nodes.is_empty() {
+        report.fail(
+            "There are no /dev/input/event* nodes",
+            "Check that the evdev module is loaded: sudo modprobe evdev",
+        );
+        return;
+    }
+
+    let unreadable = nodes
+        .iter()
+        .filter(|path| access(*path, AccessFlags::R_OK).is_err())
+        .collect::<Vec<_>>();
+    let Some(first) = unreadable.first() else {
+        report.ok(format!(
+            "All the {} /dev/input/event* nodes are readable",
+            nodes.len()
+        ));
+        return;
+    };
+
+    let (gid, mode) = fs::metadata(first)
+        .map(|metadata| (metadata.gid(), metadata.permissions().mode()))
+        .unwrap_or_default();
+    report.fail(
+        format!(
+            "{} of the {} /dev/input/event* nodes are not readable, like {} of group {}",
+            unreadable.len(),
+            nodes.len(),
+            first.display(),
+            group_name(gid)
+        ),
+        group_fix(user, gid, mode & 0o040 != 0),
+    );
+}
+
+/// A grab fails with EBUSY when another process has grabbed the device
+fn check_grabs(report: &mut Report, device_query: Option<String>) {
+    let devices = match device_query {
+        Some(device_query) if device_query.starts_with('/') => {
+            let path = fs::canonicalize(&device_query).unwrap_or(PathBuf::from(&device_query));
+            let devices = DeviceInfo::enumerate_all()
+                .into_iter()
+                .filter(|device| device.path == path)
+                .collect::<Vec<_>>();
+            if devices.is_empty() {
+                report.fail(
+                    format!("{device_query} is not an input device"),
+                    "Choose a device from `theclicker list`",
+                );
+            }
+            devices
+        }
+        Some(device_query) => match device_query
+            .parse::<DeviceQuery>()
+            .map(|query| query.find(DeviceInfo::enumerate()))
+        {
+            Ok(Ok(device)) => vec![device],
+            Ok(Err(FindError::NotFound)) => {
+                report.fail(
+                    format!("No device matches {device_query}"),
+                    "Choose a device from `theclicker list`",
+                );
+                return;
+            }
+            Ok(Err(FindError::Ambiguous(devices))) => {
+                report.fail(
+                    format!("{} devices match {device_query}", devices.len()),
+                    "Use a more specific query, or the path from `theclicker list`",
+                );
+                return;
+            }
+            Err(err) => {
+                report.fail(
+                    format!("Invalid device query {device_query}: {err}"),
+                    "See `theclicker run --help`",
+                );
+                return;
+            }
+        },
+        None => DeviceInfo::enumerate()
+            .into_iter()
+            .filter(|device| device.capabilities.events.get(EventKind::Key))
+            .filter(|device| access(&device.path, AccessFlags::R_OK).is_ok())
+            .collect(),
+    };
+
+    for device in devices {
+        let name = device.display_name();
+        let input = match InputDevice::try_open(device.path.clone()) {
+            Ok(input) => input,
+            Err(err) => {
+                report.fail(
+                    format!("Cannot open {name}: {err}"),
+                    "See the permission checks above",
+                );
+                continue;
+            }
+        };
+
+        match input.grab(true) {
+            Ok(()) => {
+                _ = input.grab(false);
+                report.ok(format!("{name} can be grabbed"));
+            }
+            Err(err) if err.raw_os_error() == Some(Errno::EBUSY as i32) => {
+                let holders = holders(&device.path);
+                report.fail(
+                    format!("{name} is grabbed by another process"),
+                    if holders.is_empty() {
+                        "Close the program that grabs it, like another TheClicker, a key remapper or a game"
+                            .to_owned()
+                    } else {
+                        format!(
+                            "Close the program that grabs it, the device is open in: {}",
+                            holders.join(", ")
+                        )
+                    },
+                );
+            }
+            Err(err) => report.fail(format!("Cannot grab {name}: {err}"), "Run without --grab"),
+        }
+    }
+}
+
+/// The processes that have the node open, only the ones of this user are visible without root
+fn holders(path: &Path) -> Vec<String> {
+    let own = std::process::id().to_string();
+    let Ok(processes) = fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+    processes
+        .filter_map(|res| res.ok())
+        .filter(|process| {
+            let pid = process.file_name();
+            pid.to_string_lossy().chars().all(|c| c.is_ascii_digit()) && pid != own.as_str()
+        })
+        .filter(|process| {
+            fs::read_dir(process.path().join("fd")).is_ok_and(|fds| {
+                fds.filter_map(|res| res.ok())
+                    .any(|fd| fs::read_link(fd.path()).is_ok_and(|target| target == path))
+            })
+        })
+        .map(|process| {
+            let comm = fs::read_to_string(process.path().join("comm")).unwrap_or_default();
+            format!(
+                "{} (pid {})",
+                comm.trim(),
+                process.file_name().to_string_lossy()
+            )
+        })
+        .collect()
+}
+
+/// How the user gets the access of the group of a node
+fn group_fix(user: Option<&User>, gid: u32, group_has_access: bool) -> String {
+    let group = group_name(gid);
+    let gid = Gid::from_raw(gid);
+    if !group_has_access || gid.as_raw() == 0 {
+        return format!(
+            "The {group} group has no access, run `sudo theclicker setup udev`, or run as root"
+        );
+    }
+
+    if getgroups().is_ok_and(|groups| groups.contains(&gid)) {
+        return format!("You are in the {group} group, check the mode and the ACLs of the node");
+    }
+
+    let member = Group::from_gid(gid).ok().flatten().is_some_and(|group| {
+        user.is_some_and(|user| group.mem.contains(&user.name) || user.gid == group.gid)
+    });
+    if member {
+        format!(
+            "You are in the {group} group, but not in this session yet, log out and log in again"
+        )
+    } else {
+        format!("sudo usermod -aG {group} $USER, then log out and log in again")
+    }
+}
+
+fn user_name(uid: u32) -> String {
+    User::from_uid(Uid::from_raw(uid))
+        .ok()
+        .flatten()
+        .map(|user| user.name)
+        .unwrap_or(uid.to_string())
+}
+
+fn group_name(gid: u32) -> String {
+    Group::from_gid(Gid::from_raw(gid))
+        .ok()
+        .flatten()
+        .map(|group| group.name)
+        .unwrap_or(gid.to_string())
+}
@@ -0,0 +1,164 @@
+use std::{
+    io,
+    os::fd::AsFd,
+    time::{Duration, Instant},
+};
+
+use nix::sys::{
+    epoll::{Epoll, EpollCreateFlags, EpollEvent, EpollFlags, EpollTimeout},
+    inotify::{AddWatchFlags, InitFlags, Inotify},
+    signal::{SigSet, Signal},
+    signalfd::SignalFd,
+    time::TimeSpec,
+    timerfd::{ClockId, Expiration, TimerFd, TimerFlags, TimerSetTimeFlags},
+};
+
+/// What woke up the event loop
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Source {
+    /// A deadline of the autoclicker
+    Timer,
+    /// SIGINT, SIGTERM or SIGHUP
+    Signal,
+    /// The input device with this index can be read
+    Input(u32),
+    /// The virtual device with this index has LED or force-feedback events
+    Feedback(u32),
+    /// A device node was created, or its permissions changed
+    Hotplug,
+}
+
+impl Source {
+    fn token(self) -> u64 {
+        let (kind, index) = match self {
+            Source::Timer => (0, 0),
+            Source::Signal => (1, 0),
+            Source::Input(index) => (2, index),
+            Source::Feedback(index) => (3, index),
+            Source::Hotplug => (4, 0),
+        };
+        (kind << 32) | index as u64
+    }
+
+    fn from_token(token: u64) -> Self {
+        let index = token as u32;
+        match token >> 32 {
+            0 => Source::Timer,
+            1 => Source::Signal,
+            2 => Source::Input(index),
+            3 => Source::Feedback(index),
+            _ => Source::Hotplug,
+        }
+    }
+}
+
+/// Waits on every file descriptor from a single thread, the deadlines are a timerfd
+/// and the termination signals a signalfd
+pub struct EventLoop {
+    epoll: Epoll,
+    timer: TimerFd,
+    signals: SignalFd,
+    hotplug: Option<Inotify>,
+}
+
+impl EventLoop {
+    /// Blocks the termination signals, after this they are only received by the loop
+    pub fn new() -> io::Result<Self> {
+        let epoll = Epoll::new(EpollCreateFlags::EPOLL_CLOEXEC)?;
+
+        let timer = TimerFd::new(
+            ClockId::CLOCK_MONOTONIC,
+            TimerFlags::TFD_NONBLOCK | TimerFlags::TFD_CLOEXEC,
+        )?;
+
+        let mut mask = SigSet::empty();
+        mask.add(Signal::SIGINT);
+        mask.add(Signal::SIGTERM);
+        mask.add(Signal::SIGHUP);
+        mask.thread_block()?;
+        let signals = SignalFd::new(&mask)?;
+
+        let event_loop = Self {
+            epoll,
+            timer,
+            signals,
+            hotplug: None,
+        };
+        event_loop.add(&event_loop.timer, Source::Timer)?;
+        event_loop.add(&event_loop.signals, Source::Signal)?;
+        Ok(event_loop)
+    }
+
+    pub fn add(&self, fd: impl AsFd, source: Source) -> io::Result<()> {
+        self.epoll
+            .add(fd, EpollEvent::new(EpollFlags::EPOLLIN, source.token()))?;
+        Ok(())
+    }
+
+    pub fn remove(&self, fd: impl AsFd) -> io::Result<()> {
+        self.epoll.delete(fd)?;
+        Ok(())
+    }
+
+    /// Wakes up with `Source::Hotplug` when a device appears in `/dev/input`
+    pub fn watch_hotplug(&mut self) -> io::Result<()> {
+        let inotify = Inotify::init(InitFlags::IN_NONBLOCK | InitFlags::IN_CLOEXEC)?;
+        // The permissions are set by udev after the node is created
+        let flags = AddWatchFlags::IN_CREATE | AddWatchFlags::IN_ATTRIB;
+        inotify.add_watch("/dev/input", flags)?;
+        for dir in ["/dev/input/by-id", "/dev/input/by-path"] {
+            // These do not exist when there is no device of that kind
+            _ = inotify.add_watch(dir, flags);
+        }
+        self.add(&inotify, Source::Hotplug)?;
+        self.hotplug = Some(inotify);
+        Ok(())
+    }
+
+    /// Waits until a source is ready, or until the deadline
+    pub fn wait(&self, deadline: Option<Instant>) -> io::Result<Vec<Source>> {
+        match deadline {
+            Some(deadline) => {
+                // A zero expiration disarms the timer
+                let timeout = deadline
+                    .saturating_duration_since(Instant::now())
+                    .max(Duration::from_nanos(1));
+                self.timer.set(
+                    Expiration::OneShot(TimeSpec::from_duration(timeout)),
+                    TimerSetTimeFlags::empty(),
+                )?;
+            }
+            None => self.timer.unset()?,
+        }
+
+        let mut events = [EpollEvent::empty(); 16];
+        let len = loop {
+            match self.epoll.wait(&mut events, EpollTimeout::NONE) {
+                Ok(len) => break len,
+                Err(nix::errno::Errno::EINTR) => continue,
+                Err(err) => return Err(err.into()),
+            }
+        };
+
+        let mut sources = Vec::with_capacity(len);
+        for event in &events[..len] {
+            let source = Source::from_token(event.data());
+            match source {
+                Source::Timer => {
+                    // Clears the expiration, the timer is non-blocking
+                    _ = nix::unistd::read(&self.timer, &mut [0; 8]);
+                }
+                Source::Signal if self.signals.read_signal()?.is_none() => continue,
+                Source::Hotplug => {
+                    if let Some(inotify) = &self.hotplug {
+                        // Only the wake up matters, the devices are looked up again
+                        while inotify.read_events().is_ok() {}
+                    }
+                }
+                _ => {}
+            }
+            sources.push(source);
+        }
+        Ok(sources)
+    }
+}
@@ -1,22 +1,50 @@
+mod absolute;
 mod args;
+mod bind;
+mod decode;
 mod device;
+mod doctor;
+mod event_loop;
+mod list;
+mod listen;
+mod motion;
+mod names;
+mod point;
+mod privileges;
+mod query;
+mod sandbox;
+mod setup;
 
 pub use args::Args;
 
 use std::{
+    collections::HashMap,
     io::{IsTerminal, Write, stdout},
-    os::fd::AsRawFd,
     path::PathBuf,
-    sync::{Arc, mpsc},
-    thread,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
+use device::{DeviceInfo, WHEEL_HI_RES_DETENT};
 pub use device::{DeviceType, InputDevice, OutputDevice};
-use input_linux::{Key, KeyState, sys::input_event};
+use input_linux::{
+    Bitmask, InputEvent, Key, KeyEvent, KeyState, SynchronizeEvent,
+    sys::{EV_KEY, EV_MSC, EV_SYN, SYN_DROPPED, SYN_REPORT, input_event},
+};
+
+use absolute::{Absolute, Touch};
+use args::{MotionPattern, ScrollDirection};
+use bind::Bind;
+use decode::Decoded;
+use event_loop::{EventLoop, Source};
+use motion::Motion;
+use point::Target;
+use query::{DeviceQuery, FindError};
 
 const WAIT_KEY_RELEASE: std::time::Duration = std::time::Duration::from_millis(100);
 
+/// How many events are read from the input device at once
+const EVENTS_BATCH: usize = 64;
+
 pub struct KeyCode(u16);
 
 impl std::fmt::Display for KeyCode {
@@ -35,243 +63,853 @@ pub struct AutoclickerState {
     left: bool,
     middle: bool,
     right: bool,
+    scroll: bool,
+    jiggle: bool,
+    motion: bool,
     lock: bool,
 }
 
-pub struct StateNormal {
-    left_bind: Option<u16>,
-    middle_bind: Option<u16>,
-    right_bind: Option<u16>,
+impl AutoclickerState {
+    fn clicking(&self) -> bool {
+        self.left | self.middle | self.right
+    }
 
-    lock_unlock_bind: Option<u16>,
+    /// The slots, in the same order as `StateNormal::binds`
+    fn slots_mut(&mut self) -> [&mut bool; 6] {
+        [
+            &mut self.left,
+            &mut self.right,
+            &mut self.middle,
+            &mut self.scroll,
+            &mut self.jiggle,
+            &mut self.motion,
+        ]
+    }
+}
 
-    hold: bool,
-    grab: bool,
+pub struct Scroll {
+    direction: ScrollDirection,
+    /// In detents, or in 1/120 of a detent when `hi_res`
+    amount: i32,
+    hi_res: bool,
+    cooldown: Duration,
+}
 
+pub struct Jiggle {
+    interval: Duration,
+    amplitude: i32,
+    /// Tap this key instead of moving the cursor
+    key: Option<Key>,
+}
+
+/// What the autoclicker does for the active slots
+pub struct Actions {
     cooldown: Duration,
     cooldown_pr: Duration,
+    scroll: Option<Scroll>,
+    jiggle: Option<Jiggle>,
+    motion: Option<Motion>,
+    /// When not empty, the clicks are sent by the absolute device, at every target in rotation
+    targets: Vec<Target>,
 }
 
-impl StateNormal {
-    pub fn run(self, shared: Shared) {
-        let (transmitter, receiver) = mpsc::channel::<AutoclickerState>();
+pub struct StateNormal {
+    /// The queries of the input devices, used to find them again after a disconnect
+    device_query: Vec<String>,
 
-        let mut events: [input_event; 1] = unsafe { std::mem::zeroed() };
-        let input = shared.input;
-        let output = shared.output.clone();
+    left_bind: Option<Bind>,
+    middle_bind: Option<Bind>,
+    right_bind: Option<Bind>,
+    scroll_bind: Option<Bind>,
+    jiggle_bind: Option<Bind>,
+    motion_bind: Option<Bind>,
 
-        let left_bind = self.left_bind;
-        let middle_bind = self.middle_bind;
-        let right_bind = self.right_bind;
+    lock_unlock_bind: Option<Bind>,
 
-        let debug = shared.debug;
-        let grab = self.grab;
+    hold: bool,
+    /// Whether the device at every index is grabbed
+    grab: Vec<bool>,
 
-        let mut state = AutoclickerState::default();
-        let hold = self.hold;
-
-        state.lock = self.lock_unlock_bind.is_some();
-        _ = transmitter.send(state);
-
-        thread::spawn(move || {
-            loop {
-                let len = match input.read(&mut events) {
-                    Ok(len) => len,
-                    Err(err) => {
-                        eprintln!("\x1B[1;31mCaptured device error: {err}\x1B[22;39m");
-                        eprintln!("\x1B[1;33mThe Clicker will terminate!\x1B[22;39m");
-                        eprintln!();
-                        std::process::exit(1);
-                    }
+    actions: Actions,
+}
+
+impl StateNormal {
+    /// The bindings of the device at `index`, in the same order as `AutoclickerState::slots_mut`
+    fn binds(&self, index: usize) -> [Option<u16>; 6] {
+        [
+            self.left_bind,
+            self.right_bind,
+            self.middle_bind,
+            self.scroll_bind,
+            self.jiggle_bind,
+            self.motion_bind,
+        ]
+        .map(|bind| bind.and_then(|bind| bind.on(index)))
+    }
+
+    pub fn run(self, shared: Shared) {
+        let Shared {
+            debug,
+            beep,
+            sandbox,
+            user,
+            inputs,
+            output,
+            passthroughs,
+            mut absolute,
+            event_loop,
+        } = shared;
+
+        for (index, input) in inputs.iter().enumerate() {
+            event_loop
+                .add(&input.handler, Source::Input(index as u32))
+                .expect("Cannot wait for the input device!");
+        }
+        // A disconnected device is reopened only if it is the same device,
+        // another device can get its node
+        let identities = inputs.iter().map(InputDevice::identity).collect::<Vec<_>>();
+        // A disconnected device is `None`, until it is opened again
+        let mut inputs = inputs.into_iter().map(Some).collect::<Vec<_>>();
+
+        // The virtual devices are in /dev/input too, and a passthrough has the name of its grabbed device,
+        // with its legacy nodes
+        let ignore = std::iter::once(&output)
+            .chain(passthroughs.iter().flatten())
+            .chain(absolute.as_ref().map(|absolute| &absolute.device))
+            .filter_map(|device| device.handler.evdev_path().ok())
+            .flat_map(|path| DeviceInfo::nodes(&path))
+            .collect::<Vec<_>>();
+
+        // With --split every grabbed device has its own passthrough device,
+        // otherwise all the grabbed input goes to the output, and its feedback to all the grabbed devices
+        let grabbed = (0..inputs.len())
+            .filter(|index| self.grab[*index])
+            .collect::<Vec<_>>();
+        let feedback = if passthroughs.iter().all(Option::is_none) {
+            vec![(&output, grabbed)]
+        } else {
+            grabbed
+                .into_iter()
+                .filter_map(|index| Some((passthroughs[index].as_ref()?, vec![index])))
+                .collect()
+        };
+        for (index, (device, to)) in feedback.iter().enumerate() {
+            if to.is_empty() {
+                continue;
+            }
+            if !device.readable {
+                eprintln!(
+                    "\x1B[1;33mCannot read /dev/uinput, the LEDs and force-feedback of {} are not forwarded\x1B[22;39m",
+                    device.name
+                );
+                continue;
+            }
+            event_loop
+                .add(&device.handler, Source::Feedback(index as u32))
+                .expect("Cannot wait for the virtual device!");
+        }
+        // The effect ids of every virtual device, to the effect ids of the grabbed device
+        let mut effects = vec![HashMap::new(); feedback.len()];
+        // The slots that were on when a device disconnected, turned on again when it reconnects,
+        // with hold they follow the buttons that are released by then
+        let mut paused = vec![None; inputs.len()];
+        // The errors of reopening every device, each is printed once per disconnect
+        let mut reopen_errors = vec![Vec::new(); inputs.len()];
+
+        let mut readers = (0..inputs.len())
+            .map(|index| Reader {
+                binds: self.binds(index),
+                lock_unlock_bind: self.lock_unlock_bind.and_then(|bind| bind.on(index)),
+                hold: self.hold,
+                grab: self.grab[index],
+                debug,
+                frame: Vec::with_capacity(EVENTS_BATCH),
+                frames: Vec::with_capacity(EVENTS_BATCH),
+                dropped: false,
+                forwarded: Bitmask::default(),
+            })
+            .collect::<Vec<_>>();
+
+        let mut autoclicker = Autoclicker::new(beep, &output, absolute.as_mut(), self.actions);
+        autoclicker.set_state(AutoclickerState {
+            lock: self.lock_unlock_bind.is_some(),
+            ..Default::default()
+        });
+
+        autoclicker.run(&event_loop, |source, state| match source {
+            Source::Input(index) => {
+                let index = index as usize;
+                let Some(input) = &inputs[index] else {
+                    return;
                 };
+                let passthrough = passthroughs[index].as_ref().unwrap_or(&output);
+                if let Err(err) = readers[index].read(input, passthrough, state) {
+                    eprintln!(
+                        "\x1B[1;31mCaptured device error: {}: {err}\x1B[22;39m",
+                        input.name
+                    );
+                    if sandbox {
+                        eprintln!(
+                            "\x1B[1;33mPaused, the sandbox cannot reopen the device, restart TheClicker\x1B[22;39m"
+                        );
+                    } else {
+                        eprintln!(
+                            "\x1B[1;33mPaused, waiting for the device to reconnect\x1B[22;39m"
+                        );
+                        if let Some(user) = &user {
+                            eprintln!(
+                                "\x1B[1;33mRunning as {user}, that may not be allowed to open the device again, use --user root or `sudo theclicker setup udev -d {:?}`\x1B[22;39m",
+                                self.device_query[index]
+                            );
+                        }
+                    }
+                    reopen_errors[index].clear();
+                    eprintln!();
+                    _ = event_loop.remove(&input.handler);
+                    readers[index].disconnect(passthrough);
+                    inputs[index] = None;
+                    let slots = state.slots_mut().map(std::mem::take);
+                    if !self.hold {
+                        paused[index] = Some(slots);
+                    }
+                }
+            }
+            Source::Feedback(index) => {
+                let index = index as usize;
+                let (device, to) = &feedback[index];
+                let to = to
+                    .iter()
+                    .filter_map(|index| inputs[*index].as_ref())
+                    .collect::<Vec<_>>();
+                if let Err(err) = device.forward_feedback(&to, debug, &mut effects[index]) {
+                    eprintln!("\x1B[1;31mCannot forward LEDs and force-feedback: {err}\x1B[22;39m");
+                }
+            }
+            Source::Hotplug => {
+                for index in 0..inputs.len() {
+                    if inputs[index].is_some() {
+                        continue;
+                    }
+                    // Two queries can match the same device, the opened ones are skipped
+                    let ignore = ignore
+                        .iter()
+                        .cloned()
+                        .chain(inputs.iter().flatten().map(|input| input.path.clone()))
+                        .collect::<Vec<_>>();
+                    let input = match InputDevice::reopen(
+                        &self.device_query[index],
+                        &ignore,
+                        &identities[index],
+                    ) {
+                        Some(Ok(input)) => input,
+                        Some(Err(err)) => {
+                            report_reopen_error(
+                                &mut reopen_errors[index],
+                                format!("Cannot open {}: {err}", self.device_query[index]),
+                            );
+                            continue;
+                        }
+                        None => continue,
+                    };
+                    if self.grab[index]
+                        && let Err(err) = input.grab(true)
+                    {
+                        report_reopen_error(
+                            &mut reopen_errors[index],
+                            format!("Cannot grab {}: {err}", input.name),
+                        );
+                        continue;
+                    }
+                    if event_loop
+                        .add(&input.handler, Source::Input(index as u32))
+                        .is_err()
+                    {
+                        continue;
+                    }
+                    eprintln!("\x1B[1;32mReconnected: {}\x1B[22;39m", input.name);
+                    eprintln!();
+                    inputs[index] = Some(input);
 
-                for event in &events[0..len] {
-                    if debug {
-                        println!("Event: {:?}", event);
+                    if let Some(slots) = paused[index].take() {
+                        for (slot, on) in state.slots_mut().into_iter().zip(slots) {
+                            *slot |= on;
+                        }
+                    }
+                    // The reopened device has none of the uploaded effects
+                    for ((_, to), effects) in feedback.iter().zip(&mut effects) {
+                        if to.contains(&index) {
+                            effects.clear();
+                        }
                     }
+                }
+            }
+            _ => {}
+        });
+    }
+}
 
-                    let mut used = false;
-                    let old_state = state;
-
-                    let pressed = matches!(event.value, 1 | 2);
-
-                    if !state.lock {
-                        for (bind, state) in [
-                            (left_bind, &mut state.left),
-                            (right_bind, &mut state.right),
-                            (middle_bind, &mut state.middle),
-                        ] {
-                            if let Some(bind) = bind
-                                && event.code == bind
-                            {
-                                if hold {
-                                    if pressed != *state {
-                                        *state = pressed;
-                                    }
-                                } else if pressed {
-                                    *state = !*state;
-                                }
-                                used = true;
-                            }
+/// Prints the error, unless it was printed since the device disconnected,
+/// the first attempts can fail until udev sets the permissions of the node
+fn report_reopen_error(reported: &mut Vec<String>, err: String) {
+    if reported.contains(&err) {
+        return;
+    }
+    eprintln!("\x1B[1;31m{err}\x1B[22;39m");
+    reported.push(err);
+}
+
+/// Turns the events of the input device into the autoclicker state,
+/// and forwards the rest of the events when the device is grabbed
+struct Reader {
+    binds: [Option<u16>; 6],
+    lock_unlock_bind: Option<u16>,
+    hold: bool,
+    grab: bool,
+    debug: bool,
+
+    /// The events of the frame that is not complete yet, without the bindings
+    frame: Vec<input_event>,
+    /// The complete frames, that will be written at once
+    frames: Vec<input_event>,
+    dropped: bool,
+    /// The keys that are pressed on the virtual device
+    forwarded: Bitmask<Key>,
+}
+
+impl Reader {
+    /// Reads one batch of events
+    fn read(
+        &mut self,
+        input: &InputDevice,
+        output: &OutputDevice,
+        state: &mut AutoclickerState,
+    ) -> std::io::Result<()> {
+        let mut events: [input_event; EVENTS_BATCH] = unsafe { std::mem::zeroed() };
+        let len = input.read(&mut events)?;
+
+        self.process(&events[0..len], state, || input.key_state());
+
+        if !self.frames.is_empty() {
+            output
+                .write(&self.frames)
+                .expect("Cannot write to virtual device!");
+            self.frames.clear();
+        }
+
+        Ok(())
+    }
+
+    /// Updates the state from the events, and when grabbed, adds the complete frames
+    /// without the bindings to `frames`, the incomplete frame waits for the next events.
+    /// After `SYN_DROPPED` the keys are taken from `key_state`, the kernel state
+    fn process(
+        &mut self,
+        events: &[input_event],
+        state: &mut AutoclickerState,
+        key_state: impl Fn() -> std::io::Result<Bitmask<Key>>,
+    ) {
+        for event in events {
+            if event.type_ == EV_SYN as u16 && event.code == SYN_DROPPED as u16 {
+                self.trace(event, "");
+                // The kernel buffer overflowed, the events until the next report are not a valid frame
+                self.dropped = true;
+                self.frame.clear();
+                continue;
+            }
+            if self.dropped {
+                self.trace(event, " (dropped)");
+                if event.type_ == EV_SYN as u16 && event.code == SYN_REPORT as u16 {
+                    self.dropped = false;
+                    match key_state() {
+                        Ok(keys) => self.resync(keys, state),
+                        Err(err) => {
+                            eprintln!("\x1B[1;31mCannot resync the key state: {err}\x1B[22;39m")
                         }
                     }
+                }
+                continue;
+            }
+
+            let mut used = false;
+
+            let is_key = event.type_ == EV_KEY as u16;
+            let pressed = matches!(event.value, 1 | 2);
 
-                    if let Some(bind) = self.lock_unlock_bind
+            if is_key && !state.lock {
+                for (bind, state) in self.binds.into_iter().zip(state.slots_mut()) {
+                    if let Some(bind) = bind
                         && event.code == bind
-                        && pressed
                     {
-                        state.lock = !state.lock;
+                        if self.hold {
+                            if pressed != *state {
+                                *state = pressed;
+                            }
+                        } else if pressed {
+                            *state = !*state;
+                        }
+                        used = true;
                     }
+                }
+            }
 
-                    if old_state != state {
-                        transmitter.send(state).unwrap();
-                    }
+            if let Some(bind) = self.lock_unlock_bind
+                && is_key
+                && event.code == bind
+                && pressed
+            {
+                state.lock = !state.lock;
+            }
+
+            match (used, self.grab) {
+                (true, true) => self.trace(event, " (bind, swallowed)"),
+                (true, false) => self.trace(event, " (bind)"),
+                _ => self.trace(event, ""),
+            }
+
+            if !self.grab {
+                continue;
+            }
 
-                    if grab && !used {
-                        output
-                            .write(&events)
-                            .expect("Cannot write to virtual device!");
+            if !used {
+                self.frame.push(*event);
+            }
+
+            if event.type_ == EV_SYN as u16 && event.code == SYN_REPORT as u16 {
+                // A frame that only had bindings, has nothing to report
+                if self
+                    .frame
+                    .iter()
+                    .any(|event| !matches!(event.type_ as i32, EV_SYN | EV_MSC))
+                {
+                    for event in self
+                        .frame
+                        .iter()
+                        .filter(|event| event.type_ == EV_KEY as u16)
+                    {
+                        if let Ok(key) = Key::from_code(event.code) {
+                            match event.value {
+                                0 => self.forwarded.remove(key),
+                                1 => self.forwarded.insert(key),
+                                _ => {}
+                            }
+                        }
                     }
+                    self.frames.append(&mut self.frame);
                 }
+                self.frame.clear();
             }
-        });
+        }
+    }
 
-        autoclicker(
-            shared.beep,
-            receiver,
-            &shared.output,
-            self.cooldown,
-            self.cooldown_pr,
-        );
+    fn trace(&self, event: &input_event, note: &str) {
+        if self.debug {
+            println!("Event: {}{note}", Decoded(event));
+        }
+    }
+
+    /// The device is gone, the keys that it pressed on the virtual device are released
+    fn disconnect(&mut self, output: &OutputDevice) {
+        self.frame.clear();
+        self.dropped = false;
+        if !self.grab || self.forwarded.iter().next().is_none() {
+            return;
+        }
+
+        let time = device::get_current_time();
+        let mut events = self
+            .forwarded
+            .iter()
+            .map(|key| *InputEvent::from(KeyEvent::new(time, key, KeyState::RELEASED)).as_raw())
+            .collect::<Vec<_>>();
+        events.push(*InputEvent::from(SynchronizeEvent::report(time)).as_raw());
+        _ = output.write(&events);
+        self.forwarded = Bitmask::default();
+    }
+
+    /// Press and release events were lost, the state is taken from the kernel
+    fn resync(&mut self, keys: Bitmask<Key>, state: &mut AutoclickerState) {
+        if self.hold && !state.lock {
+            for (bind, state) in self.binds.into_iter().zip(state.slots_mut()) {
+                if let Some(bind) = bind
+                    && let Ok(key) = Key::from_code(bind)
+                {
+                    *state = keys.get(key);
+                }
+            }
+        }
+
+        if !self.grab {
+            return;
+        }
+
+        let time = device::get_current_time();
+        let changes = resync_changes(&self.forwarded, &self.binds, &keys);
+        if !changes.is_empty() {
+            for (key, key_state) in changes {
+                self.frames
+                    .push(*InputEvent::from(KeyEvent::new(time, key, key_state)).as_raw());
+            }
+            self.frames
+                .push(*InputEvent::from(SynchronizeEvent::report(time)).as_raw());
+        }
+
+        self.forwarded = keys;
+        for key in self.binds.iter().flatten() {
+            if let Ok(key) = Key::from_code(*key) {
+                self.forwarded.remove(key);
+            }
+        }
     }
 }
 
+/// What the virtual device must press and release, so its keys are the `keys` of the kernel,
+/// the bindings are never pressed
+fn resync_changes(
+    forwarded: &Bitmask<Key>,
+    binds: &[Option<u16>],
+    keys: &Bitmask<Key>,
+) -> Vec<(Key, KeyState)> {
+    let is_bind = |key: Key| binds.contains(&Some(key as u16));
+    let released = forwarded
+        .iter()
+        .filter(|key| !keys.get(*key))
+        .map(|key| (key, KeyState::RELEASED));
+    let pressed = keys
+        .iter()
+        .filter(|key| !forwarded.get(*key) && !is_bind(*key))
+        .map(|key| (key, KeyState::PRESSED));
+    released.chain(pressed).collect()
+}
+
 pub struct StateLegacy {
-    cooldown: Duration,
-    cooldown_pr: Duration,
+    actions: Actions,
 }
 
 impl StateLegacy {
-    fn run(self, shared: Shared) {
-        let (transmitter, receiver) = mpsc::channel::<AutoclickerState>();
+    fn run(self, mut shared: Shared) {
+        let input = &shared.inputs[0];
 
-        let input = shared.input;
+        let event_loop = &shared.event_loop;
+        event_loop
+            .add(&input.handler, Source::Input(0))
+            .expect("Cannot wait for the input device!");
 
-        let fd = input.handler.as_inner().as_raw_fd();
         let mut data: [u8; 3] = [0; 3];
-        let mut state = AutoclickerState {
-            lock: true,
-            ..Default::default()
-        };
-        transmitter.send(state).unwrap();
 
         let mut old_left = 0;
         let mut old_right = 0;
         let mut old_middle = 0;
 
-        std::thread::spawn(move || {
-            let fd = unsafe { std::os::fd::BorrowedFd::borrow_raw(fd) };
-            loop {
-                let Ok(len) = nix::unistd::read(fd, &mut data) else {
-                    panic!("Cannot read from input device!");
-                };
+        let mut autoclicker = Autoclicker::new(
+            shared.beep,
+            &shared.output,
+            shared.absolute.as_mut(),
+            self.actions,
+        );
+        autoclicker.set_state(AutoclickerState {
+            lock: true,
+            ..Default::default()
+        });
 
-                if len != 3 {
-                    continue;
-                }
+        autoclicker.run(event_loop, |source, state| {
+            if source != Source::Input(0) {
+                return;
+            }
 
-                let left = data[0] & 1;
-                let right = (data[0] >> 1) & 1;
-                let middle = (data[0] >> 2) & 1;
+            let Ok(len) = nix::unistd::read(&input.handler, &mut data) else {
+                panic!("Cannot read from input device!");
+            };
 
-                let old_state = state;
+            if len != 3 {
+                return;
+            }
 
-                if !state.lock {
-                    for (value, old_value, state) in [
-                        (left, old_left, &mut state.left),
-                        (right, old_right, &mut state.right),
-                    ] {
-                        if value == 1 && old_value == 0 {
-                            *state = !*state;
-                        }
+            let left = data[0] & 1;
+            let right = (data[0] >> 1) & 1;
+            let middle = (data[0] >> 2) & 1;
+
+            if !state.lock {
+                for (value, old_value, state) in [
+                    (left, old_left, &mut state.left),
+                    (right, old_right, &mut state.right),
+                ] {
+                    if value == 1 && old_value == 0 {
+                        *state = !*state;
                     }
                 }
+            }
 
-                if middle == 1 && old_middle == 0 {
-                    state.lock = !state.lock;
-                }
+            if middle == 1 && old_middle == 0 {
+                state.lock = !state.lock;
+            }
 
-                old_left = left;
-                old_right = right;
-                old_middle = middle;
+            old_left = left;
+            old_right = right;
+            old_middle = middle;
+        });
+    }
+}
+
+/// Does the actions of the active slots, every action has its own deadline
+struct Autoclicker<'a> {
+    beep: bool,
+    output: &'a OutputDevice,
+    absolute: Option<&'a mut Absolute>,
+    actions: Actions,
+
+    toggle: AutoclickerState,
+
+    next_click: Instant,
+    /// The keys of the click in progress, that will be released
+    pressed: Option<Vec<Key>>,
+    /// The next target of the absolute device
+    target: usize,
+    next_scroll: Instant,
+    /// The high-resolution scroll that did not add up to a full detent yet
+    scroll_remainder: i32,
+    next_jiggle: Instant,
+    next_motion: Instant,
+}
 
-                if old_state != state {
-                    transmitter.send(state).unwrap();
+impl<'a> Autoclicker<'a> {
+    fn new(
+        beep: bool,
+        output: &'a OutputDevice,
+        absolute: Option<&'a mut Absolute>,
+        actions: Actions,
+    ) -> Self {
+        let now = Instant::now();
+        Self {
+            beep,
+            output,
+            absolute,
+            actions,
+            toggle: AutoclickerState::default(),
+            next_click: now,
+            pressed: None,
+            target: 0,
+            next_scroll: now,
+            scroll_remainder: 0,
+            next_jiggle: now,
+            next_motion: now,
+        }
+    }
+
+    /// Waits for the event loop until a termination signal, `handle` changes the state for the other sources
+    fn run(
+        &mut self,
+        event_loop: &EventLoop,
+        mut handle: impl FnMut(Source, &mut AutoclickerState),
+    ) {
+        println!();
+        print_active(&self.toggle);
+
+        loop {
+            let sources = match event_loop.wait(self.deadline()) {
+                Ok(sources) => sources,
+                Err(err) => {
+                    eprintln!("\x1B[1;31mEvent loop error: {err}\x1B[22;39m");
+                    self.stop();
+                    std::process::exit(1);
+                }
+            };
+
+            let mut state = self.toggle;
+            for source in sources {
+                match source {
+                    Source::Signal => {
+                        self.stop();
+                        return;
+                    }
+                    Source::Timer => {}
+                    source => handle(source, &mut state),
                 }
             }
-        });
+            self.set_state(state);
 
-        autoclicker(
-            shared.beep,
-            receiver,
-            &shared.output,
-            self.cooldown,
-            self.cooldown_pr,
-        );
+            self.run_due();
+        }
     }
-}
 
-fn autoclicker(
-    beep: bool,
-    receiver: std::sync::mpsc::Receiver<AutoclickerState>,
-    output: &OutputDevice,
-    cooldown: Duration,
-    cooldown_pr: Duration,
-) {
-    let mut toggle = AutoclickerState::default();
-    println!();
-    print_active(&toggle);
+    fn set_state(&mut self, state: AutoclickerState) {
+        if state == self.toggle {
+            return;
+        }
 
-    loop {
-        if let Some(recv) = if toggle.left | toggle.middle | toggle.right {
-            receiver.try_recv().ok()
-        } else {
-            receiver.recv().ok()
-        } {
-            toggle = recv;
+        let now = Instant::now();
+        if state.clicking() && !self.toggle.clicking() && self.pressed.is_none() {
+            self.next_click = now;
+        }
+        if state.scroll && !self.toggle.scroll {
+            self.next_scroll = now;
+            self.scroll_remainder = 0;
+        }
+        if state.jiggle && !self.toggle.jiggle {
+            self.next_jiggle = now;
+        }
+        if state.motion != self.toggle.motion
+            && let Some(motion) = &mut self.actions.motion
+        {
+            // Releases the button of an unfinished drag
+            if motion.drag && !motion.cycle_start() {
+                self.output.send_key(Key::ButtonLeft, KeyState::RELEASED);
+            }
+            motion.reset();
+            self.next_motion = now;
+        }
+
+        self.toggle = state;
+
+        if self.beep {
+            // ansi beep sound
+            print!("\x07");
+        }
+
+        print_active(&self.toggle);
+    }
+
+    fn deadline(&self) -> Option<Instant> {
+        let toggle = &self.toggle;
+        let actions = &self.actions;
+        [
+            (toggle.clicking() || self.pressed.is_some()).then_some(self.next_click),
+            (toggle.scroll && actions.scroll.is_some()).then_some(self.next_scroll),
+            (toggle.jiggle && actions.jiggle.is_some()).then_some(self.next_jiggle),
+            (toggle.motion && actions.motion.is_some()).then_some(self.next_motion),
+        ]
+        .into_iter()
+        .flatten()
+        .min()
+    }
+
+    /// Does every action that reached its deadline, once
+    fn run_due(&mut self) {
+        let now = Instant::now();
+        let toggle = self.toggle;
+        let output = self.output;
 
-            if beep {
-                // ansi beep sound
-                print!("\x07");
+        if let Some(scroll) = &self.actions.scroll
+            && toggle.scroll
+            && now >= self.next_scroll
+        {
+            if scroll.hi_res {
+                self.scroll_remainder += scroll.amount;
+                let detents = self.scroll_remainder / WHEEL_HI_RES_DETENT;
+                self.scroll_remainder %= WHEEL_HI_RES_DETENT;
+                output.send_scroll(scroll.direction, detents, Some(scroll.amount));
+            } else {
+                output.send_scroll(scroll.direction, scroll.amount, None);
             }
+            self.next_scroll = (self.next_scroll + scroll.cooldown).max(now);
+        }
 
-            print_active(&toggle);
+        if let Some(jiggle) = &self.actions.jiggle
+            && toggle.jiggle
+            && now >= self.next_jiggle
+        {
+            if let Some(key) = jiggle.key {
+                output.send_key(key, KeyState::PRESSED);
+                output.send_key(key, KeyState::RELEASED);
+            } else {
+                output.send_motion(jiggle.amplitude, jiggle.amplitude);
+                output.send_motion(-jiggle.amplitude, -jiggle.amplitude);
+            }
+            self.next_jiggle = now + jiggle.interval;
         }
 
-        if toggle.left {
-            output.send_key(Key::ButtonLeft, KeyState::PRESSED);
+        if let Some(motion) = &mut self.actions.motion
+            && toggle.motion
+            && now >= self.next_motion
+        {
+            if motion.drag && motion.cycle_start() {
+                output.send_key(Key::ButtonLeft, KeyState::PRESSED);
+            }
+            let ((dx, dy), ended) = motion.next_step();
+            output.send_motion(dx, dy);
+            if motion.drag && ended {
+                output.send_key(Key::ButtonLeft, KeyState::RELEASED);
+            }
+            self.next_motion = (self.next_motion + motion.cooldown).max(now);
         }
-        if toggle.middle {
-            output.send_key(Key::ButtonMiddle, KeyState::PRESSED);
+
+        if (toggle.clicking() || self.pressed.is_some()) && now >= self.next_click {
+            self.click(now);
         }
-        if toggle.right {
-            output.send_key(Key::ButtonRight, KeyState::PRESSED);
+    }
+
+    /// Presses, moves the swipe or releases, depending on the click in progress
+    fn click(&mut self, now: Instant) {
+        let Some(keys) = self.pressed.take() else {
+            let keys = [
+                (self.toggle.left, Key::ButtonLeft),
+                (self.toggle.middle, Key::ButtonMiddle),
+                (self.toggle.right, Key::ButtonRight),
+            ]
+            .into_iter()
+            .filter_map(|(active, key)| active.then_some(key))
+            .collect::<Vec<_>>();
+
+            if let Some(absolute) = &mut self.absolute {
+                let target = self.actions.targets[self.target];
+                self.target = (self.target + 1) % self.actions.targets.len();
+                absolute.press(target, &keys);
+            } else {
+                for key in keys.iter() {
+                    self.output.send_key(*key, KeyState::PRESSED);
+                }
+            }
+            self.pressed = Some(keys);
+            return self.after_press(now);
+        };
+
+        if let Some(absolute) = &mut self.absolute
+            && absolute.swiping()
+        {
+            absolute.step();
+            self.pressed = Some(keys);
+            return self.after_press(now);
         }
 
-        if !cooldown_pr.is_zero() {
-            thread::sleep(cooldown_pr);
+        self.release(&keys, now);
+    }
+
+    fn after_press(&mut self, now: Instant) {
+        if let Some(absolute) = &self.absolute
+            && absolute.swiping()
+        {
+            self.next_click = now + absolute.swipe_cooldown;
+        } else if self.actions.cooldown_pr.is_zero() {
+            let keys = self.pressed.take().unwrap_or_default();
+            self.release(&keys, now);
+        } else {
+            self.next_click = now + self.actions.cooldown_pr;
         }
+    }
 
-        if toggle.left {
-            output.send_key(Key::ButtonLeft, KeyState::RELEASED);
+    fn release(&mut self, keys: &[Key], now: Instant) {
+        if let Some(absolute) = &mut self.absolute {
+            absolute.release(keys);
+        } else {
+            for key in keys {
+                self.output.send_key(*key, KeyState::RELEASED);
+            }
         }
-        if toggle.middle {
-            output.send_key(Key::ButtonMiddle, KeyState::RELEASED);
+        self.next_click = now + self.actions.cooldown;
+    }
+
+    /// Releases everything that is pressed
+    fn stop(&mut self) {
+        if let Some(keys) = self.pressed.take() {
+            self.release(&keys, Instant::now());
         }
-        if toggle.right {
-            output.send_key(Key::ButtonRight, KeyState::RELEASED);
+        if let Some(motion) = &mut self.actions.motion
+            && motion.drag
+            && !motion.cycle_start()
+        {
+            self.output.send_key(Key::ButtonLeft, KeyState::RELEASED);
+            motion.reset();
         }
-        thread::sleep(cooldown);
+        println!();
     }
 }
 
@@ -292,8 +930,19 @@ impl Variant {
 pub struct Shared {
     debug: bool,
     beep: bool,
-    input: InputDevice,
-    output: Arc<OutputDevice>,
+    /// Files cannot be opened, so the devices are not reopened
+    sandbox: bool,
+    /// The user that the privileges were dropped to, it may not be allowed to reopen the devices
+    user: Option<String>,
+    inputs: Vec<InputDevice>,
+    output: OutputDevice,
+    /// The clones of the grabbed devices, when the clicks have a separate device,
+    /// at the index of their input device
+    passthroughs: Vec<Option<OutputDevice>>,
+    /// The tablet or touchscreen, used for clicking at points
+    absolute: Option<Absolute>,
+    /// Created before the sandbox, that does not allow creating the epoll, the timerfd and the signalfd
+    event_loop: EventLoop,
 }
 
 pub struct TheClicker {
@@ -306,10 +955,44 @@ impl TheClicker {
         Args {
             debug,
             beep,
+            user,
+            sandbox,
+            identity,
             command,
         }: Args,
     ) -> Self {
-        let output = OutputDevice::uinput_open(PathBuf::from("/dev/uinput"), "TheClicker").unwrap();
+        // These only inspect the devices, without the virtual device
+        let command = match command {
+            Some(args::Command::List { json }) => {
+                list::list_devices(json);
+                std::process::exit(0);
+            }
+            Some(args::Command::Listen { device_query, .. }) => {
+                listen::listen(device_query);
+                std::process::exit(0);
+            }
+            Some(args::Command::Doctor { device_query }) => {
+                doctor::doctor(device_query);
+                std::process::exit(0);
+            }
+            Some(args::Command::Setup {
+                target:
+                    args::SetupTarget::Udev {
+                        device_query,
+                        group,
+                        print,
+                        path,
+                    },
+            }) => {
+                setup::udev(device_query, group, print, &path);
+                std::process::exit(0);
+            }
+            command => command,
+        };
+
+        let mut output =
+            OutputDevice::uinput_open(PathBuf::from("/dev/uinput"), "TheClicker").unwrap();
+        output.trace = debug;
 
         let command = command.unwrap_or_else(command_from_user_input);
 
@@ -320,20 +1003,80 @@ impl TheClicker {
         if beep {
             print!("--beep ")
         }
-        match command {
+        if let Some(user) = &user {
+            print!("--user {user} ")
+        }
+        if sandbox {
+            print!("--sandbox ")
+        }
+        print!("{identity}");
+        let mut clicker = match command {
+            args::Command::List { .. }
+            | args::Command::Listen { .. }
+            | args::Command::Doctor { .. }
+            | args::Command::Setup { .. } => {
+                unreachable!("handled before opening uinput")
+            }
             args::Command::Run {
                 device_query,
                 left_bind,
                 middle_bind,
                 right_bind,
                 lock_unlock_bind,
+                scroll_bind,
+                scroll_direction,
+                scroll_cooldown,
+                scroll_amount,
+                scroll_hi_res,
+                jiggle_bind,
+                jiggle_interval,
+                jiggle_amplitude,
+                jiggle_key,
+                motion_bind,
+                motion,
+                motion_dx,
+                motion_dy,
+                motion_radius,
+                motion_steps,
+                motion_cooldown,
+                drag,
+                targets,
+                touchscreen,
+                fingers,
+                finger_spacing,
+                swipe_steps,
+                swipe_cooldown,
                 hold,
                 grab,
+                split,
+                mirror,
                 cooldown,
                 cooldown_press_release,
             } => {
                 output.add_mouse_attributes(false);
-                print!("run -d{device_query:?} -c{cooldown} -C{cooldown_press_release}");
+                if scroll_bind.is_some() {
+                    output.add_scroll_attributes(scroll_hi_res);
+                }
+                let jiggle_key = jiggle_key.map(|code| {
+                    Key::from_code(code).unwrap_or_else(|_| {
+                        eprintln!("Invalid jiggle key: {code}");
+                        std::process::exit(6);
+                    })
+                });
+                if jiggle_bind.is_some() {
+                    match jiggle_key {
+                        Some(key) => output.add_key_attributes(key),
+                        None => output.add_motion_attributes(),
+                    }
+                }
+                if motion_bind.is_some() {
+                    output.add_motion_attributes();
+                }
+                print!("run");
+                for query in device_query.iter() {
+                    print!(" -d{query:?}")
+                }
+                print!(" -c{cooldown} -C{cooldown_press_release}");
 
                 if let Some(bind) = left_bind {
                     print!(" -l{bind}")
@@ -347,43 +1090,255 @@ impl TheClicker {
                 if let Some(bind) = lock_unlock_bind {
                     print!(" -T{bind}")
                 }
+                if let Some(bind) = scroll_bind {
+                    print!(
+                        " -s{bind} --scroll-direction {scroll_direction} --scroll-cooldown {scroll_cooldown} --scroll-amount {scroll_amount}"
+                    );
+                    if scroll_hi_res {
+                        print!(" --scroll-hi-res")
+                    }
+                }
+                if let Some(bind) = jiggle_bind {
+                    print!(
+                        " -j{bind} --jiggle-interval {jiggle_interval} --jiggle-amplitude {jiggle_amplitude}"
+                    );
+                    if let Some(key) = jiggle_key {
+                        print!(" --jiggle-key {}", key as u16)
+                    }
+                }
+                if let Some(bind) = motion_bind {
+                    print!(
+                        " -M{bind} --motion {motion} --motion-dx {motion_dx} --motion-dy {motion_dy} --motion-radius {motion_radius} --motion-steps {motion_steps} --motion-cooldown {motion_cooldown}"
+                    );
+                    if drag {
+                        print!(" --drag")
+                    }
+                }
+                for target in targets.iter() {
+                    print!(" --point {target}")
+                }
+                if touchscreen {
+                    print!(" --touchscreen --fingers {fingers} --finger-spacing {finger_spacing}")
+                }
+                if !targets.is_empty() {
+                    print!(" --swipe-steps {swipe_steps} --swipe-cooldown {swipe_cooldown}")
+                }
                 if hold {
                     print!(" -H")
                 }
-                if grab {
-                    print!(" --grab")
+                match &grab {
+                    Some(indexes) if indexes.is_empty() => print!(" --grab"),
+                    Some(indexes) => {
+                        for index in indexes {
+                            print!(" --grab {index}")
+                        }
+                    }
+                    None => {}
+                }
+                if split {
+                    print!(" --split")
+                }
+                if mirror {
+                    print!(" --mirror")
                 }
                 println!("`");
 
-                let input = input_device_from_query(device_query);
-                if input.filename.starts_with("mouse") && input.filename.as_str() == "mice" {
+                for bind in [
+                    left_bind,
+                    middle_bind,
+                    right_bind,
+                    lock_unlock_bind,
+                    scroll_bind,
+                    jiggle_bind,
+                    motion_bind,
+                ]
+                .into_iter()
+                .flatten()
+                {
+                    if let Some(device) = bind.device
+                        && device as usize >= device_query.len()
+                    {
+                        eprintln!(
+                            "Invalid bind: {bind}, there are only {} devices, starting from 0",
+                            device_query.len()
+                        );
+                        std::process::exit(7);
+                    }
+                }
+                if let Some(index) = grab
+                    .iter()
+                    .flatten()
+                    .find(|index| **index as usize >= device_query.len())
+                {
+                    eprintln!(
+                        "Invalid --grab {index}, there are only {} devices, starting from 0",
+                        device_query.len()
+                    );
+                    std::process::exit(7);
+                }
+                // `--grab` without indexes grabs every device
+                let grab = (0..device_query.len())
+                    .map(|index| {
+                        grab.as_ref().is_some_and(|indexes| {
+                            indexes.is_empty() || indexes.contains(&(index as u8))
+                        })
+                    })
+                    .collect::<Vec<_>>();
+
+                let inputs = device_query
+                    .iter()
+                    .cloned()
+                    .map(input_device_from_query)
+                    .collect::<Vec<_>>();
+                if inputs.iter().any(|input| {
+                    input.filename.starts_with("mouse") && input.filename.as_str() == "mice"
+                }) {
                     eprintln!("Use the run-legacy for legacy devices");
                     std::process::exit(4);
                 }
 
-                if grab {
-                    output.copy_attributes(debug, &input);
+                // Without any binding nothing can be activated, so the first device suggests them
+                let (left_bind, right_bind) = if [
+                    left_bind,
+                    middle_bind,
+                    right_bind,
+                    lock_unlock_bind,
+                    scroll_bind,
+                    jiggle_bind,
+                    motion_bind,
+                ]
+                .iter()
+                .all(Option::is_none)
+                {
+                    let device_type = inputs[0].device_type();
+                    let [left_key, _, right_key] = inputs[0].suggested_binds();
+                    let bind = |key: Option<Key>| {
+                        key.map(|key| Bind {
+                            code: key as u16,
+                            device: (inputs.len() > 1).then_some(0),
+                        })
+                    };
+                    let (left_bind, right_bind) = (bind(left_key), bind(right_key));
+                    if left_bind.is_none() && right_bind.is_none() {
+                        eprintln!(
+                            "\x1B[1;33mNo bindings, and none are suggested for a {device_type}, nothing can be activated\x1B[22;39m"
+                        );
+                    } else {
+                        print!("No bindings, using the suggested ones for a {device_type}:");
+                        if let Some(bind) = left_bind {
+                            print!(" -l{bind}")
+                        }
+                        if let Some(bind) = right_bind {
+                            print!(" -r{bind}")
+                        }
+                        println!();
+                    }
+                    (left_bind, right_bind)
+                } else {
+                    (left_bind, right_bind)
+                };
+
+                let mut passthroughs = Vec::new();
+                for (input, grab) in inputs.iter().zip(&grab) {
+                    if !grab {
+                        passthroughs.push(None);
+                        continue;
+                    }
+                    if split {
+                        let mut passthrough = OutputDevice::uinput_open(
+                            PathBuf::from("/dev/uinput"),
+                            &input.device_name(),
+                        )
+                        .unwrap();
+                        passthrough.trace = debug;
+                        passthrough.copy_attributes(debug, input);
+                        passthrough.mirror_identity(input);
+                        passthroughs.push(Some(passthrough));
+                    } else {
+                        output.copy_attributes(debug, input);
+                        passthroughs.push(None);
+                    }
                     input.grab(true).expect("Cannot grab input device!");
                 }
+                if mirror && let Some(index) = grab.iter().position(|grab| *grab) {
+                    output.mirror_identity(&inputs[index]);
+                }
+                output.set_identity(&identity);
 
                 output.create();
+                for passthrough in passthroughs.iter().flatten() {
+                    passthrough.create();
+                }
+                let absolute = (!targets.is_empty()).then(|| {
+                    let mut absolute = Absolute::open(
+                        touchscreen.then_some(Touch {
+                            fingers,
+                            spacing: finger_spacing,
+                        }),
+                        swipe_steps,
+                        Duration::from_millis(swipe_cooldown),
+                    );
+                    absolute.device.trace = debug;
+                    absolute
+                });
+
+                let mut event_loop = EventLoop::new().expect("Cannot create the event loop!");
+                if !sandbox && let Err(err) = event_loop.watch_hotplug() {
+                    eprintln!(
+                        "\x1B[1;33mCannot watch /dev/input, disconnected devices will not be reopened: {err}\x1B[22;39m"
+                    );
+                }
 
                 Self {
                     shared: Shared {
                         debug,
                         beep,
-                        input,
-                        output: Arc::new(output),
+                        sandbox,
+                        user: None,
+                        inputs,
+                        output,
+                        passthroughs,
+                        absolute,
+                        event_loop,
                     },
                     variant: Variant::Normal(StateNormal {
+                        device_query,
                         left_bind,
                         middle_bind,
                         right_bind,
+                        scroll_bind,
+                        jiggle_bind,
+                        motion_bind,
                         lock_unlock_bind,
                         hold,
                         grab,
-                        cooldown: Duration::from_millis(cooldown),
-                        cooldown_pr: Duration::from_millis(cooldown_press_release),
+                        actions: Actions {
+                            cooldown: Duration::from_millis(cooldown),
+                            cooldown_pr: Duration::from_millis(cooldown_press_release),
+                            scroll: scroll_bind.map(|_| Scroll {
+                                direction: scroll_direction,
+                                amount: scroll_amount,
+                                hi_res: scroll_hi_res,
+                                cooldown: Duration::from_millis(scroll_cooldown),
+                            }),
+                            jiggle: jiggle_bind.map(|_| Jiggle {
+                                interval: Duration::from_millis(jiggle_interval),
+                                amplitude: jiggle_amplitude,
+                                key: jiggle_key,
+                            }),
+                            motion: motion_bind.map(|_| {
+                                Motion::new(
+                                    motion,
+                                    motion_dx,
+                                    motion_dy,
+                                    motion_radius,
+                                    motion_steps,
+                                    Duration::from_millis(motion_cooldown),
+                                    drag,
+                                )
+                            }),
+                            targets,
+                        },
                     }),
                 }
             }
@@ -395,7 +1350,7 @@ impl TheClicker {
                 output.add_mouse_attributes(true);
                 println!("run-legacy -d{device_query:?} -c{cooldown} -C{cooldown_press_release}`");
 
-                let input = input_device_from_query(device_query);
+                let input = legacy_input_device_from_query(device_query);
                 if input.filename.as_str() == "mice" {
                     eprintln!(
                         "You cannot use the /dev/input/mice, because receivers events from all other /dev/input/mouse{{N}}"
@@ -403,22 +1358,42 @@ impl TheClicker {
                     std::process::exit(5);
                 }
 
+                output.set_identity(&identity);
                 output.create();
 
                 Self {
                     shared: Shared {
                         debug,
                         beep,
-                        input,
-                        output: Arc::new(output),
+                        sandbox,
+                        user: None,
+                        inputs: vec![input],
+                        output,
+                        passthroughs: vec![None],
+                        absolute: None,
+                        event_loop: EventLoop::new().expect("Cannot create the event loop!"),
                     },
                     variant: Variant::Legacy(StateLegacy {
-                        cooldown: Duration::from_millis(cooldown),
-                        cooldown_pr: Duration::from_millis(cooldown_press_release),
+                        actions: Actions {
+                            cooldown: Duration::from_millis(cooldown),
+                            cooldown_pr: Duration::from_millis(cooldown_press_release),
+                            scroll: None,
+                            jiggle: None,
+                            motion: None,
+                            targets: Vec::new(),
+                        },
                     }),
                 }
             }
+        };
+
+        // Everything that needs root is open
+        clicker.shared.user = privileges::drop_privileges(user);
+        if sandbox {
+            sandbox::sandbox();
         }
+
+        clicker
     }
 
     pub fn main_loop(self) {
@@ -427,25 +1402,108 @@ impl TheClicker {
 }
 
 fn input_device_from_query(device_query: String) -> InputDevice {
-    'try_set_input: {
-        if device_query.is_empty() {
-            eprintln!("Device query is empty!");
-            std::process::exit(1);
+    if device_query.is_empty() {
+        eprintln!("Device query is empty!");
+        std::process::exit(1);
+    }
+
+    if device_query.starts_with('/') {
+        let Ok(device) = InputDevice::dev_open(PathBuf::from(&device_query)) else {
+            eprintln!("Cannot open device: {device_query}");
+            std::process::exit(2);
+        };
+        device
+    } else {
+        InputDevice::dev_open(device_info_from_query(&device_query).path).unwrap()
+    }
+}
+
+/// Like `input_device_from_query`, but the query matches the legacy `mouseN` nodes,
+/// the evdev nodes cannot be read as a legacy device
+fn legacy_input_device_from_query(device_query: String) -> InputDevice {
+    if device_query.is_empty() {
+        eprintln!("Device query is empty!");
+        std::process::exit(1);
+    }
+
+    let path = if device_query.starts_with('/') {
+        PathBuf::from(&device_query)
+    } else {
+        legacy_device_info_from_query(&device_query).path
+    };
+    let input = InputDevice::dev_open(path).unwrap();
+    if input.filename.starts_with("event") {
+        eprintln!(
+            "{} is an evdev device, run-legacy reads only the legacy /dev/input/mouse{{N}}",
+            input.path.display()
+        );
+        eprintln!("Use the run for evdev devices");
+        std::process::exit(4);
+    }
+    input
+}
+
+/// The device of a query or of a path, without opening it
+fn device_info_from_query(device_query: &str) -> DeviceInfo {
+    if device_query.starts_with('/') {
+        let path = std::fs::canonicalize(device_query).unwrap_or(PathBuf::from(device_query));
+        let Some(device) = DeviceInfo::enumerate_all()
+            .into_iter()
+            .find(|device| device.path == path)
+        else {
+            eprintln!("Cannot find device: {device_query}");
+            std::process::exit(2);
+        };
+        return device;
+    }
+
+    let query = match device_query.parse::<DeviceQuery>() {
+        Ok(query) => query,
+        Err(err) => {
+            eprintln!("Invalid device query: {device_query}: {err}");
+            std::process::exit(3);
         }
+    };
+    found_device(device_query, query.find(DeviceInfo::enumerate()))
+}
 
-        if device_query.starts_with('/') {
-            let Ok(device) = InputDevice::dev_open(PathBuf::from(&device_query)) else {
-                eprintln!("Cannot open device: {device_query}");
-                std::process::exit(2);
-            };
-            break 'try_set_input device;
-        } else {
-            let Some(device) = InputDevice::find_device(&device_query) else {
-                eprintln!("Cannot find device: {device_query}");
+/// The legacy `mouseN` of a query
+fn legacy_device_info_from_query(device_query: &str) -> DeviceInfo {
+    let query = match device_query.parse::<DeviceQuery>() {
+        Ok(query) => query,
+        Err(err) => {
+            eprintln!("Invalid device query: {device_query}: {err}");
+            std::process::exit(3);
+        }
+    };
+    found_device(
+        device_query,
+        query.find_legacy_mouse(DeviceInfo::enumerate_all()),
+    )
+}
 
-                std::process::exit(3);
-            };
-            break 'try_set_input device;
+/// The found device, or exits explaining why there is none
+fn found_device(device_query: &str, found: Result<DeviceInfo, FindError>) -> DeviceInfo {
+    match found {
+        Ok(device) => device,
+        Err(FindError::NotFound) => {
+            eprintln!("Cannot find device: {device_query}");
+
+            std::process::exit(3);
+        }
+        Err(FindError::Ambiguous(devices)) => {
+            eprintln!("The device query matches more devices: {device_query}");
+            for device in devices {
+                eprintln!(
+                    "\t{}: {} ({:04x}:{:04x})",
+                    device.path.display(),
+                    device.display_name(),
+                    device.id.vendor,
+                    device.id.product
+                );
+            }
+            eprintln!("Use the path, or a more specific query");
+            std::process::exit(8);
         }
     }
 }
@@ -461,16 +1519,18 @@ fn print_active(toggle: &AutoclickerState) {
     if toggle.lock {
         print!("LOCKED: ")
     }
-    if toggle.left {
-        print!("left ")
-    }
-    if toggle.right {
-        if toggle.left {
-            print!(", ")
-        }
-        print!("right")
-    }
-    println!();
+    let active = [
+        (toggle.left, "left"),
+        (toggle.middle, "middle"),
+        (toggle.right, "right"),
+        (toggle.scroll, "scroll"),
+        (toggle.jiggle, "jiggle"),
+        (toggle.motion, "motion"),
+    ]
+    .into_iter()
+    .filter_map(|(active, name)| active.then_some(name))
+    .collect::<Vec<_>>();
+    println!("{}", active.join(", "));
 
     if is_terminal {
         print!("\x1b[1F");
@@ -486,9 +1546,14 @@ fn command_from_user_input() -> args::Command {
 
     if legacy {
         eprintln!("\x1B[1;31mUsing legacy interface for PS/2 device\x1B[0;39m");
-        let cooldown = choose_usize("Choose cooldown, the min is 25", Some(25)) as u64;
-        let cooldown_press_release =
-            choose_usize("Choose cooldown between press and release", Some(0)) as u64;
+        let cooldown = choose_usize(
+            "Choose cooldown, the min is 25",
+            Some(args::COOLDOWN as usize),
+        ) as u64;
+        let cooldown_press_release = choose_usize(
+            "Choose cooldown between press and release",
+            Some(args::COOLDOWN_PRESS_RELEASE as usize),
+        ) as u64;
 
         args::Command::RunLegacy {
             device_query: input_device.path.to_str().unwrap().to_owned(),
@@ -496,18 +1561,28 @@ fn command_from_user_input() -> args::Command {
             cooldown_press_release,
         }
     } else {
+        let device_type = input_device.device_type();
+        println!("Device type: {device_type}");
+        let [left_key, middle_key, right_key] = input_device.suggested_binds();
+
         let lock_unlock_bind = choose_yes(
             "Lock Unlock mode, useful for mouse without side buttons",
-            false,
+            device_type.is_mouse() && left_key.is_none(),
         )
-        .then(|| choose_key(&input_device, "lock_unlock_bind"));
+        .then(|| choose_key(&input_device, "lock_unlock_bind", None));
 
         let left_bind = choose_yes("You want a binding for left autoclicker?", true)
-            .then(|| choose_key(&input_device, "left_bind"));
+            .then(|| choose_key(&input_device, "left_bind", left_key));
         let middle_bind = choose_yes("You want a binding for middle autoclicker?", false)
-            .then(|| choose_key(&input_device, "middle_bind"));
+            .then(|| choose_key(&input_device, "middle_bind", middle_key));
         let right_bind = choose_yes("You want a binding for right autoclicker?", true)
-            .then(|| choose_key(&input_device, "right_bind"));
+            .then(|| choose_key(&input_device, "right_bind", right_key));
+        let scroll_bind = choose_yes("You want a binding for auto-scroll?", false)
+            .then(|| choose_key(&input_device, "scroll_bind", None));
+        let jiggle_bind = choose_yes("You want a binding for the mouse jiggler?", false)
+            .then(|| choose_key(&input_device, "jiggle_bind", None));
+        let motion_bind = choose_yes("You want a binding for motion?", false)
+            .then(|| choose_key(&input_device, "motion_bind", None));
         let hold = choose_yes("You want to hold the bind / active hold_mode?", true);
         println!(
             "\x1B[1;33mWarning: if you enable grab mode you can get softlocked\x1B[0;39m, if the compositor will not use TheClicker device."
@@ -517,7 +1592,10 @@ fn command_from_user_input() -> args::Command {
         );
         let grab = choose_yes("You want to grab the input device?", true);
         println!("Grab: {grab}");
-        let mut cooldown = choose_usize("Choose cooldown, the min is 25", Some(25)) as u64;
+        let mut cooldown = choose_usize(
+            "Choose cooldown, the min is 25",
+            Some(args::COOLDOWN as usize),
+        ) as u64;
         if cooldown < 25 {
             cooldown = 25;
             println!("\x1B[1;39mThe cooldown was set to \x1B[1;32m25\x1B[0;39m");
@@ -528,8 +1606,10 @@ fn command_from_user_input() -> args::Command {
                 "\x1B[;32mIf your kernel permits that, you can bypass this dialog using the command args and modify the -c argument.\x1B[;39m"
             );
         }
-        let cooldown_press_release =
-            choose_usize("Choose cooldown between press and release", Some(0)) as u64;
+        let cooldown_press_release = choose_usize(
+            "Choose cooldown between press and release",
+            Some(args::COOLDOWN_PRESS_RELEASE as usize),
+        ) as u64;
 
         std::thread::sleep(WAIT_KEY_RELEASE);
 
@@ -538,16 +1618,50 @@ fn command_from_user_input() -> args::Command {
             right_bind,
             middle_bind,
             hold,
-            grab,
+            grab: grab.then(Vec::new),
+            split: false,
+            mirror: false,
             lock_unlock_bind,
+            scroll_bind,
+            scroll_direction: ScrollDirection::default(),
+            scroll_cooldown: args::SCROLL_COOLDOWN,
+            scroll_amount: args::SCROLL_AMOUNT,
+            scroll_hi_res: false,
+            jiggle_bind,
+            jiggle_interval: args::JIGGLE_INTERVAL,
+            jiggle_amplitude: args::JIGGLE_AMPLITUDE,
+            jiggle_key: None,
+            motion_bind,
+            motion: MotionPattern::default(),
+            motion_dx: args::MOTION_DX,
+            motion_dy: args::MOTION_DY,
+            motion_radius: args::MOTION_RADIUS,
+            motion_steps: args::MOTION_STEPS,
+            motion_cooldown: args::MOTION_COOLDOWN,
+            drag: false,
+            targets: Vec::new(),
+            touchscreen: false,
+            fingers: args::FINGERS,
+            finger_spacing: args::FINGER_SPACING,
+            swipe_steps: args::SWIPE_STEPS,
+            swipe_cooldown: args::SWIPE_COOLDOWN,
             cooldown,
             cooldown_press_release,
-            device_query: input_device.path.to_str().unwrap().to_owned(),
+            device_query: vec![input_device.path.to_str().unwrap().to_owned()],
         }
     }
 }
 
-fn choose_key(input_device: &InputDevice, name: &str) -> u16 {
+fn choose_key(input_device: &InputDevice, name: &str, suggested: Option<Key>) -> Bind {
+    if let Some(key) = suggested
+        && choose_yes(format!("Use {} for {name}?", KeyCode(key as u16)), true)
+    {
+        return Bind {
+            code: key as u16,
+            device: None,
+        };
+    }
+
     let mut events: [input_linux::sys::input_event; 1] = unsafe { std::mem::zeroed() };
     std::thread::sleep(WAIT_KEY_RELEASE);
     println!("\x1B[1;33mWaiting for key presses from the selected device\x1B[22;39m");
@@ -590,7 +1704,10 @@ fn choose_key(input_device: &InputDevice, name: &str) -> u16 {
         }
 
         if choose_yes("You want to choose this", true) {
-            break events[0].code;
+            break Bind {
+                code: events[0].code,
+                device: None,
+            };
         }
     }
 }
@@ -646,3 +1763,218 @@ fn choose_usize(message: impl std::fmt::Display, default: Option<usize>) -> usiz
         return num;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use input_linux::{EventTime, MiscEvent, MiscKind, RelativeAxis, RelativeEvent};
+
+    use super::*;
+
+    const TIME: EventTime = EventTime::new(0, 0);
+
+    fn key(key: Key, value: KeyState) -> input_event {
+        *InputEvent::from(KeyEvent::new(TIME, key, value)).as_raw()
+    }
+
+    fn rel(value: i32) -> input_event {
+        *InputEvent::from(RelativeEvent::new(TIME, RelativeAxis::X, value)).as_raw()
+    }
+
+    fn scan() -> input_event {
+        *InputEvent::from(MiscEvent::new(TIME, MiscKind::Scancode, 0x90004)).as_raw()
+    }
+
+    fn report() -> input_event {
+        *InputEvent::from(SynchronizeEvent::report(TIME)).as_raw()
+    }
+
+    fn dropped() -> input_event {
+        let mut event = report();
+        event.code = SYN_DROPPED as u16;
+        event
+    }
+
+    /// Grabbed, with `BTN_SIDE` bound to the left autoclicker
+    fn reader() -> Reader {
+        Reader {
+            binds: [Some(Key::ButtonSide as u16), None, None, None, None, None],
+            lock_unlock_bind: None,
+            hold: true,
+            grab: true,
+            debug: false,
+            frame: Vec::new(),
+            frames: Vec::new(),
+            dropped: false,
+            forwarded: Bitmask::default(),
+        }
+    }
+
+    fn no_keys() -> std::io::Result<Bitmask<Key>> {
+        Ok(Bitmask::default())
+    }
+
+    /// As `(type, code, value)`, to compare
+    fn raw(events: &[input_event]) -> Vec<(u16, u16, i32)> {
+        events
+            .iter()
+            .map(|event| (event.type_, event.code, event.value))
+            .collect()
+    }
+
+    #[test]
+    fn bound_keys_are_swallowed() {
+        let mut reader = reader();
+        let mut state = AutoclickerState::default();
+
+        reader.process(
+            &[key(Key::ButtonSide, KeyState::PRESSED), rel(3), report()],
+            &mut state,
+            no_keys,
+        );
+        assert!(state.left);
+        assert_eq!(raw(&reader.frames), raw(&[rel(3), report()]));
+        assert!(!reader.forwarded.get(Key::ButtonSide));
+    }
+
+    #[test]
+    fn frames_of_only_bindings_are_dropped() {
+        let mut reader = reader();
+        let mut state = AutoclickerState::default();
+
+        reader.process(
+            &[
+                scan(),
+                key(Key::ButtonSide, KeyState::PRESSED),
+                report(),
+                scan(),
+                key(Key::ButtonSide, KeyState::RELEASED),
+                report(),
+            ],
+            &mut state,
+            no_keys,
+        );
+        assert!(!state.left);
+        assert!(raw(&reader.frames).is_empty());
+        assert!(reader.frame.is_empty());
+    }
+
+    #[test]
+    fn partial_frames_wait_for_the_next_read() {
+        let mut reader = reader();
+        let mut state = AutoclickerState::default();
+
+        reader.process(
+            &[rel(1), report(), key(Key::ButtonLeft, KeyState::PRESSED)],
+            &mut state,
+            no_keys,
+        );
+        assert_eq!(raw(&reader.frames), raw(&[rel(1), report()]));
+        assert!(!reader.forwarded.get(Key::ButtonLeft));
+
+        reader.process(&[rel(2), report()], &mut state, no_keys);
+        assert_eq!(
+            raw(&reader.frames),
+            raw(&[
+                rel(1),
+                report(),
+                key(Key::ButtonLeft, KeyState::PRESSED),
+                rel(2),
+                report()
+            ])
+        );
+        assert!(reader.forwarded.get(Key::ButtonLeft));
+    }
+
+    #[test]
+    fn events_after_syn_dropped_are_discarded() {
+        let mut reader = reader();
+        let mut state = AutoclickerState::default();
+
+        reader.process(
+            &[
+                rel(1),
+                dropped(),
+                rel(2),
+                key(Key::ButtonLeft, KeyState::PRESSED),
+            ],
+            &mut state,
+            no_keys,
+        );
+        assert!(raw(&reader.frames).is_empty());
+        assert!(reader.dropped);
+
+        // The frame ends in the next read, then the keys come from the kernel state
+        reader.process(&[report(), rel(4), report()], &mut state, no_keys);
+        assert!(!reader.dropped);
+        assert_eq!(raw(&reader.frames), raw(&[rel(4), report()]));
+        assert!(!reader.forwarded.get(Key::ButtonLeft));
+    }
+
+    #[test]
+    fn not_grabbed_forwards_nothing() {
+        let mut reader = Reader {
+            grab: false,
+            ..reader()
+        };
+        let mut state = AutoclickerState::default();
+
+        reader.process(
+            &[key(Key::ButtonSide, KeyState::PRESSED), rel(3), report()],
+            &mut state,
+            no_keys,
+        );
+        assert!(state.left);
+        assert!(raw(&reader.frames).is_empty());
+    }
+
+    fn keys(keys: &[Key]) -> Bitmask<Key> {
+        let mut bitmask = Bitmask::default();
+        for key in keys {
+            bitmask.insert(*key);
+        }
+        bitmask
+    }
+
+    #[test]
+    fn resync_releases_and_presses_the_lost_keys() {
+        let binds = [Some(Key::ButtonSide as u16)];
+
+        // Released during the drop
+        assert_eq!(
+            resync_changes(&keys(&[Key::A, Key::B]), &binds, &keys(&[Key::B])),
+            [(Key::A, KeyState::RELEASED)]
+        );
+        // Pressed during the drop
+        assert_eq!(
+            resync_changes(&keys(&[Key::A]), &binds, &keys(&[Key::A, Key::C])),
+            [(Key::C, KeyState::PRESSED)]
+        );
+        // The bindings are never forwarded
+        assert_eq!(
+            resync_changes(&keys(&[]), &binds, &keys(&[Key::ButtonSide])),
+            []
+        );
+    }
+
+    #[test]
+    fn resync_holds_the_slots_and_forwards_the_keys() {
+        let mut reader = reader();
+        reader.forwarded = keys(&[Key::ButtonLeft]);
+        let mut state = AutoclickerState::default();
+
+        reader.resync(keys(&[Key::ButtonSide, Key::ButtonRight]), &mut state);
+        assert!(state.left);
+        assert_eq!(
+            raw(&reader.frames),
+            raw(&[
+                key(Key::ButtonLeft, KeyState::RELEASED),
+                key(Key::ButtonRight, KeyState::PRESSED),
+                report()
+            ])
+        );
+        assert_eq!(
+            reader.forwarded.iter().collect::<Vec<_>>(),
+            [Key::ButtonRight]
+        );
+    }
+}
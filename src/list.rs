@@ -0,0 +1,144 @@
+use nix::unistd::{AccessFlags, access};
+
+use crate::{args::Bus, device::DeviceInfo};
+
+/// A device node in `/dev/input`, with its sysfs description
+struct Entry {
+    info: DeviceInfo,
+    /// Whether the node can be opened by this user, or why not
+    access: Result<(), String>,
+}
+
+impl Entry {
+    fn kind(&self) -> String {
+        if self.info.filename.starts_with("mouse") {
+            return "legacy".to_owned();
+        }
+        self.info.device_type().to_string()
+    }
+}
+
+fn entries() -> Vec<Entry> {
+    DeviceInfo::enumerate_all()
+        .into_iter()
+        .map(|info| Entry {
+            access: access(&info.path, AccessFlags::R_OK).map_err(|err| err.desc().to_owned()),
+            info,
+        })
+        .collect()
+}
+
+fn bus_name(bustype: u16) -> String {
+    match Bus::from_bustype(bustype) {
+        Some(bus) => bus.to_string(),
+        None => format!("{bustype:#x}"),
+    }
+}
+
+pub fn list_devices(json: bool) {
+    let entries = entries();
+    if json {
+        println!("{}", to_json(&entries));
+        return;
+    }
+
+    if entries.is_empty() {
+        println!("No input devices found in /dev/input");
+    }
+    for entry in entries.iter() {
+        let info = &entry.info;
+        match &entry.access {
+            Ok(()) => println!("\x1B[1;39m{}\x1B[0;39m: {}", info.path.display(), info.name),
+            Err(err) => println!(
+                "\x1B[1;39m{}\x1B[0;39m: {} \x1B[1;31m(not accessible: {err})\x1B[0;39m",
+                info.path.display(),
+                info.name
+            ),
+        }
+        for link in info.links.iter() {
+            println!("\tlink: {}", link.display());
+        }
+        println!("\ttype: {}", entry.kind());
+        println!(
+            "\tid: {} {:04x}:{:04x}",
+            bus_name(info.id.bustype),
+            info.id.vendor,
+            info.id.product
+        );
+        if !info.phys.is_empty() {
+            println!("\tphys: {}", info.phys);
+        }
+        if !info.uniq.is_empty() {
+            println!("\tuniq: {}", info.uniq);
+        }
+        let buttons = info
+            .capabilities
+            .buttons()
+            .map(|key| format!("{} {key:?}", key as u16))
+            .collect::<Vec<_>>();
+        if !buttons.is_empty() {
+            println!("\tbuttons: {}", buttons.join(", "));
+        }
+    }
+}
+
+fn to_json(entries: &[Entry]) -> String {
+    let devices = entries
+        .iter()
+        .map(|entry| {
+            let info = &entry.info;
+            let mut fields = vec![
+                format!("\"path\":{}", json_string(&info.path.to_string_lossy())),
+                format!("\"name\":{}", json_string(&info.name)),
+                format!(
+                    "\"links\":[{}]",
+                    info.links
+                        .iter()
+                        .map(|link| json_string(&link.to_string_lossy()))
+                        .collect::<Vec<_>>()
+                        .join(",")
+                ),
+                format!("\"type\":{}", json_string(&entry.kind())),
+                format!("\"bus\":{}", json_string(&bus_name(info.id.bustype))),
+                format!("\"vendor\":\"{:04x}\"", info.id.vendor),
+                format!("\"product\":\"{:04x}\"", info.id.product),
+                format!("\"phys\":{}", json_string(&info.phys)),
+                format!("\"uniq\":{}", json_string(&info.uniq)),
+                format!(
+                    "\"buttons\":[{}]",
+                    info.capabilities
+                        .buttons()
+                        .map(|key| format!(
+                            "{{\"code\":{},\"name\":{}}}",
+                            key as u16,
+                            json_string(&format!("{key:?}"))
+                        ))
+                        .collect::<Vec<_>>()
+                        .join(",")
+                ),
+                format!("\"accessible\":{}", entry.access.is_ok()),
+            ];
+            if let Err(err) = &entry.access {
+                fields.push(format!("\"error\":{}", json_string(err)));
+            }
+            format!("{{{}}}", fields.join(","))
+        })
+        .collect::<Vec<_>>();
+    format!("[{}]", devices.join(","))
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
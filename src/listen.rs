@@ -0,0 +1,84 @@
+use input_linux::{
+    EventKind,
+    sys::{EV_KEY, input_event},
+};
+
+use crate::{
+    EVENTS_BATCH, KeyCode,
+    device::{DeviceInfo, InputDevice},
+    event_loop::{EventLoop, Source},
+    input_device_from_query,
+};
+
+/// Prints the presses of one device, or of every device with keys when `device_query` is `None`,
+/// until Ctrl+C
+pub fn listen(device_query: Option<String>) {
+    let devices = match device_query {
+        Some(device_query) => vec![input_device_from_query(device_query)],
+        None => DeviceInfo::enumerate()
+            .into_iter()
+            .filter(|info| info.capabilities.events.get(EventKind::Key))
+            .filter_map(|info| match InputDevice::try_open(info.path.clone()) {
+                Ok(device) => Some(device),
+                Err(err) => {
+                    eprintln!(
+                        "\x1B[1;33mSkipping {}: {err}\x1B[22;39m",
+                        info.path.display()
+                    );
+                    None
+                }
+            })
+            .collect(),
+    };
+    if devices.is_empty() {
+        eprintln!("\x1B[1;31mNo device to listen to, try as root!\x1B[22;39m");
+        std::process::exit(1);
+    }
+
+    let event_loop = EventLoop::new().expect("Cannot create the event loop!");
+    for (index, device) in devices.iter().enumerate() {
+        event_loop
+            .add(&device.handler, Source::Input(index as u32))
+            .expect("Cannot wait for the input device!");
+    }
+    let several = devices.len() > 1;
+    // A device that fails is `None`, like after unplugging it
+    let mut devices = devices.into_iter().map(Some).collect::<Vec<_>>();
+
+    println!("\x1B[1;33mPress the keys or buttons, Ctrl+C to stop\x1B[22;39m");
+
+    let mut events: [input_event; EVENTS_BATCH] = unsafe { std::mem::zeroed() };
+    loop {
+        let sources = event_loop.wait(None).expect("Cannot wait for the devices!");
+        for source in sources {
+            let Source::Input(index) = source else {
+                if source == Source::Signal {
+                    return;
+                }
+                continue;
+            };
+            let Some(device) = &devices[index as usize] else {
+                continue;
+            };
+
+            let len = match device.read(&mut events) {
+                Ok(len) => len,
+                Err(err) => {
+                    eprintln!("\x1B[1;31m{}: {err}\x1B[22;39m", device.name);
+                    _ = event_loop.remove(&device.handler);
+                    devices[index as usize] = None;
+                    continue;
+                }
+            };
+            for event in &events[..len] {
+                if event.type_ != EV_KEY as u16 || event.value != 1 {
+                    continue;
+                }
+                if several {
+                    print!("{}: ", device.name);
+                }
+                println!("{}\t-l {}", KeyCode(event.code), event.code);
+            }
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use std::{f64::consts::TAU, time::Duration};
+
+use crate::args::MotionPattern;
+
+pub struct Motion {
+    pub pattern: MotionPattern,
+    pub dx: i32,
+    pub dy: i32,
+    pub radius: i32,
+    pub steps: u32,
+    pub cooldown: Duration,
+    pub drag: bool,
+
+    /// The step in the current cycle
+    step: u32,
+    /// Where the cursor should be relative to the start of the cycle
+    position: (i32, i32),
+    random: u64,
+}
+
+impl Motion {
+    pub fn new(
+        pattern: MotionPattern,
+        dx: i32,
+        dy: i32,
+        radius: i32,
+        steps: u32,
+        cooldown: Duration,
+        drag: bool,
+    ) -> Self {
+        let seed = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|time| time.as_nanos() as u64)
+            .unwrap_or_default();
+
+        Self {
+            pattern,
+            dx,
+            dy,
+            radius,
+            steps,
+            cooldown,
+            drag,
+            step: 0,
+            position: (0, 0),
+            // xorshift needs a non zero state
+            random: seed | 1,
+        }
+    }
+
+    /// Starts again from the beginning of a cycle
+    pub fn reset(&mut self) {
+        self.step = 0;
+        self.position = (0, 0);
+    }
+
+    pub fn cycle_start(&self) -> bool {
+        self.step == 0
+    }
+
+    /// Returns the relative movement for the next step, and if the cycle ended
+    pub fn next_step(&mut self) -> ((i32, i32), bool) {
+        self.step += 1;
+        let t = self.step as f64 / self.steps as f64;
+
+        let target = match self.pattern {
+            MotionPattern::Line => (self.dx as f64 * t, self.dy as f64 * t),
+            MotionPattern::Circle => {
+                let radius = self.radius as f64;
+                let angle = TAU * t;
+                (radius * angle.cos() - radius, radius * angle.sin())
+            }
+            MotionPattern::Zigzag => {
+                // triangle wave: 0 -> 1 -> 0 -> -1 -> 0
+                let wave = if t < 0.25 {
+                    4.0 * t
+                } else if t < 0.75 {
+                    2.0 - 4.0 * t
+                } else {
+                    4.0 * t - 4.0
+                };
+                (
+                    self.dx as f64 * t,
+                    self.dy as f64 * t + self.radius as f64 * wave,
+                )
+            }
+            MotionPattern::RandomWalk => {
+                let angle = TAU * (self.random() as f64 / u64::MAX as f64);
+                let radius = self.radius as f64;
+                (
+                    self.position.0 as f64 + radius * angle.cos(),
+                    self.position.1 as f64 + radius * angle.sin(),
+                )
+            }
+        };
+
+        // Rounds the absolute target, so the error does not accumulate between steps
+        let target = (target.0.round() as i32, target.1.round() as i32);
+        let delta = (target.0 - self.position.0, target.1 - self.position.1);
+        self.position = target;
+
+        let ended = self.step >= self.steps;
+        if ended {
+            self.reset();
+        }
+
+        (delta, ended)
+    }
+
+    /// xorshift64
+    fn random(&mut self) -> u64 {
+        self.random ^= self.random << 13;
+        self.random ^= self.random >> 7;
+        self.random ^= self.random << 17;
+        self.random
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn motion(pattern: MotionPattern, dx: i32, dy: i32, steps: u32) -> Motion {
+        Motion::new(pattern, dx, dy, 50, steps, Duration::ZERO, false)
+    }
+
+    /// The sum of the steps of one cycle, checking that only the last step ends it
+    fn cycle(motion: &mut Motion) -> (i32, i32) {
+        let mut sum = (0, 0);
+        for step in 1..=motion.steps {
+            let ((dx, dy), ended) = motion.next_step();
+            sum = (sum.0 + dx, sum.1 + dy);
+            assert_eq!(ended, step == motion.steps, "step {step}");
+        }
+        sum
+    }
+
+    #[test]
+    fn circle_returns_to_the_start() {
+        for steps in [1, 3, 20, 37] {
+            let mut circle = motion(MotionPattern::Circle, 0, 0, steps);
+            assert_eq!(cycle(&mut circle), (0, 0));
+            assert_eq!(cycle(&mut circle), (0, 0));
+        }
+    }
+
+    #[test]
+    fn zigzag_moves_by_dx_dy() {
+        for steps in [4, 7, 20] {
+            // Without dx and dy, the up and down movement returns to the start
+            let mut zigzag = motion(MotionPattern::Zigzag, 0, 0, steps);
+            assert_eq!(cycle(&mut zigzag), (0, 0));
+
+            let mut zigzag = motion(MotionPattern::Zigzag, 100, -30, steps);
+            assert_eq!(cycle(&mut zigzag), (100, -30));
+            assert_eq!(cycle(&mut zigzag), (100, -30));
+        }
+    }
+
+    #[test]
+    fn line_moves_by_dx_dy() {
+        for steps in [1, 3, 20] {
+            let mut line = motion(MotionPattern::Line, 100, -7, steps);
+            assert_eq!(cycle(&mut line), (100, -7));
+            assert_eq!(cycle(&mut line), (100, -7));
+        }
+    }
+
+    #[test]
+    fn random_walk_ends_every_cycle() {
+        let mut random_walk = motion(MotionPattern::RandomWalk, 0, 0, 5);
+        for _ in 0..3 {
+            cycle(&mut random_walk);
+            assert!(random_walk.cycle_start());
+        }
+    }
+}
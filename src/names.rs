@@ -0,0 +1,853 @@
+//! The kernel names of the event types and codes, for the `--debug` output
+//!
+//! Generated by `python3 tools/names.py && cargo fmt` from the constants of input-linux-sys 0.9,
+//! one table for every prefix (`KEY_` and `BTN_` share one), sorted by value.
+//! The aliases and the `_MAX`/`_CNT` are left out,
+//! when more names have the same value the last one is kept, like `BTN_LEFT` for `BTN_MOUSE`.
+//! The test at the end checks that every table is still sorted, `lookup` needs it
+
+use input_linux::sys;
+
+/// The name of an event type, like `EV_KEY`
+pub fn type_name(type_: u16) -> Option<&'static str> {
+    lookup(TYPES, type_)
+}
+
+/// The name of an event code, like `BTN_SIDE`, for the event type
+pub fn code_name(type_: u16, code: u16) -> Option<&'static str> {
+    let table = match type_ as i32 {
+        sys::EV_SYN => SYN,
+        sys::EV_KEY => KEYS,
+        sys::EV_REL => REL,
+        sys::EV_ABS => ABS,
+        sys::EV_MSC => MSC,
+        sys::EV_SW => SW,
+        sys::EV_LED => LED,
+        sys::EV_SND => SND,
+        sys::EV_REP => REP,
+        sys::EV_FF => FF,
+        _ => return None,
+    };
+    lookup(table, code)
+}
+
+/// The tables are sorted by value
+fn lookup(table: &[(i32, &'static str)], value: u16) -> Option<&'static str> {
+    table
+        .binary_search_by_key(&(value as i32), |(value, _)| *value)
+        .ok()
+        .map(|index| table[index].1)
+}
+
+const TYPES: &[(i32, &str)] = &[
+    (sys::EV_SYN, "EV_SYN"),
+    (sys::EV_KEY, "EV_KEY"),
+    (sys::EV_REL, "EV_REL"),
+    (sys::EV_ABS, "EV_ABS"),
+    (sys::EV_MSC, "EV_MSC"),
+    (sys::EV_SW, "EV_SW"),
+    (sys::EV_LED, "EV_LED"),
+    (sys::EV_SND, "EV_SND"),
+    (sys::EV_REP, "EV_REP"),
+    (sys::EV_FF, "EV_FF"),
+    (sys::EV_PWR, "EV_PWR"),
+    (sys::EV_FF_STATUS, "EV_FF_STATUS"),
+];
+
+const SYN: &[(i32, &str)] = &[
+    (sys::SYN_REPORT, "SYN_REPORT"),
+    (sys::SYN_CONFIG, "SYN_CONFIG"),
+    (sys::SYN_MT_REPORT, "SYN_MT_REPORT"),
+    (sys::SYN_DROPPED, "SYN_DROPPED"),
+];
+
+const KEYS: &[(i32, &str)] = &[
+    (sys::KEY_RESERVED, "KEY_RESERVED"),
+    (sys::KEY_ESC, "KEY_ESC"),
+    (sys::KEY_1, "KEY_1"),
+    (sys::KEY_2, "KEY_2"),
+    (sys::KEY_3, "KEY_3"),
+    (sys::KEY_4, "KEY_4"),
+    (sys::KEY_5, "KEY_5"),
+    (sys::KEY_6, "KEY_6"),
+    (sys::KEY_7, "KEY_7"),
+    (sys::KEY_8, "KEY_8"),
+    (sys::KEY_9, "KEY_9"),
+    (sys::KEY_0, "KEY_0"),
+    (sys::KEY_MINUS, "KEY_MINUS"),
+    (sys::KEY_EQUAL, "KEY_EQUAL"),
+    (sys::KEY_BACKSPACE, "KEY_BACKSPACE"),
+    (sys::KEY_TAB, "KEY_TAB"),
+    (sys::KEY_Q, "KEY_Q"),
+    (sys::KEY_W, "KEY_W"),
+    (sys::KEY_E, "KEY_E"),
+    (sys::KEY_R, "KEY_R"),
+    (sys::KEY_T, "KEY_T"),
+    (sys::KEY_Y, "KEY_Y"),
+    (sys::KEY_U, "KEY_U"),
+    (sys::KEY_I, "KEY_I"),
+    (sys::KEY_O, "KEY_O"),
+    (sys::KEY_P, "KEY_P"),
+    (sys::KEY_LEFTBRACE, "KEY_LEFTBRACE"),
+    (sys::KEY_RIGHTBRACE, "KEY_RIGHTBRACE"),
+    (sys::KEY_ENTER, "KEY_ENTER"),
+    (sys::KEY_LEFTCTRL, "KEY_LEFTCTRL"),
+    (sys::KEY_A, "KEY_A"),
+    (sys::KEY_S, "KEY_S"),
+    (sys::KEY_D, "KEY_D"),
+    (sys::KEY_F, "KEY_F"),
+    (sys::KEY_G, "KEY_G"),
+    (sys::KEY_H, "KEY_H"),
+    (sys::KEY_J, "KEY_J"),
+    (sys::KEY_K, "KEY_K"),
+    (sys::KEY_L, "KEY_L"),
+    (sys::KEY_SEMICOLON, "KEY_SEMICOLON"),
+    (sys::KEY_APOSTROPHE, "KEY_APOSTROPHE"),
+    (sys::KEY_GRAVE, "KEY_GRAVE"),
+    (sys::KEY_LEFTSHIFT, "KEY_LEFTSHIFT"),
+    (sys::KEY_BACKSLASH, "KEY_BACKSLASH"),
+    (sys::KEY_Z, "KEY_Z"),
+    (sys::KEY_X, "KEY_X"),
+    (sys::KEY_C, "KEY_C"),
+    (sys::KEY_V, "KEY_V"),
+    (sys::KEY_B, "KEY_B"),
+    (sys::KEY_N, "KEY_N"),
+    (sys::KEY_M, "KEY_M"),
+    (sys::KEY_COMMA, "KEY_COMMA"),
+    (sys::KEY_DOT, "KEY_DOT"),
+    (sys::KEY_SLASH, "KEY_SLASH"),
+    (sys::KEY_RIGHTSHIFT, "KEY_RIGHTSHIFT"),
+    (sys::KEY_KPASTERISK, "KEY_KPASTERISK"),
+    (sys::KEY_LEFTALT, "KEY_LEFTALT"),
+    (sys::KEY_SPACE, "KEY_SPACE"),
+    (sys::KEY_CAPSLOCK, "KEY_CAPSLOCK"),
+    (sys::KEY_F1, "KEY_F1"),
+    (sys::KEY_F2, "KEY_F2"),
+    (sys::KEY_F3, "KEY_F3"),
+    (sys::KEY_F4, "KEY_F4"),
+    (sys::KEY_F5, "KEY_F5"),
+    (sys::KEY_F6, "KEY_F6"),
+    (sys::KEY_F7, "KEY_F7"),
+    (sys::KEY_F8, "KEY_F8"),
+    (sys::KEY_F9, "KEY_F9"),
+    (sys::KEY_F10, "KEY_F10"),
+    (sys::KEY_NUMLOCK, "KEY_NUMLOCK"),
+    (sys::KEY_SCROLLLOCK, "KEY_SCROLLLOCK"),
+    (sys::KEY_KP7, "KEY_KP7"),
+    (sys::KEY_KP8, "KEY_KP8"),
+    (sys::KEY_KP9, "KEY_KP9"),
+    (sys::KEY_KPMINUS, "KEY_KPMINUS"),
+    (sys::KEY_KP4, "KEY_KP4"),
+    (sys::KEY_KP5, "KEY_KP5"),
+    (sys::KEY_KP6, "KEY_KP6"),
+    (sys::KEY_KPPLUS, "KEY_KPPLUS"),
+    (sys::KEY_KP1, "KEY_KP1"),
+    (sys::KEY_KP2, "KEY_KP2"),
+    (sys::KEY_KP3, "KEY_KP3"),
+    (sys::KEY_KP0, "KEY_KP0"),
+    (sys::KEY_KPDOT, "KEY_KPDOT"),
+    (sys::KEY_ZENKAKUHANKAKU, "KEY_ZENKAKUHANKAKU"),
+    (sys::KEY_102ND, "KEY_102ND"),
+    (sys::KEY_F11, "KEY_F11"),
+    (sys::KEY_F12, "KEY_F12"),
+    (sys::KEY_RO, "KEY_RO"),
+    (sys::KEY_KATAKANA, "KEY_KATAKANA"),
+    (sys::KEY_HIRAGANA, "KEY_HIRAGANA"),
+    (sys::KEY_HENKAN, "KEY_HENKAN"),
+    (sys::KEY_KATAKANAHIRAGANA, "KEY_KATAKANAHIRAGANA"),
+    (sys::KEY_MUHENKAN, "KEY_MUHENKAN"),
+    (sys::KEY_KPJPCOMMA, "KEY_KPJPCOMMA"),
+    (sys::KEY_KPENTER, "KEY_KPENTER"),
+    (sys::KEY_RIGHTCTRL, "KEY_RIGHTCTRL"),
+    (sys::KEY_KPSLASH, "KEY_KPSLASH"),
+    (sys::KEY_SYSRQ, "KEY_SYSRQ"),
+    (sys::KEY_RIGHTALT, "KEY_RIGHTALT"),
+    (sys::KEY_LINEFEED, "KEY_LINEFEED"),
+    (sys::KEY_HOME, "KEY_HOME"),
+    (sys::KEY_UP, "KEY_UP"),
+    (sys::KEY_PAGEUP, "KEY_PAGEUP"),
+    (sys::KEY_LEFT, "KEY_LEFT"),
+    (sys::KEY_RIGHT, "KEY_RIGHT"),
+    (sys::KEY_END, "KEY_END"),
+    (sys::KEY_DOWN, "KEY_DOWN"),
+    (sys::KEY_PAGEDOWN, "KEY_PAGEDOWN"),
+    (sys::KEY_INSERT, "KEY_INSERT"),
+    (sys::KEY_DELETE, "KEY_DELETE"),
+    (sys::KEY_MACRO, "KEY_MACRO"),
+    (sys::KEY_MUTE, "KEY_MUTE"),
+    (sys::KEY_VOLUMEDOWN, "KEY_VOLUMEDOWN"),
+    (sys::KEY_VOLUMEUP, "KEY_VOLUMEUP"),
+    (sys::KEY_POWER, "KEY_POWER"),
+    (sys::KEY_KPEQUAL, "KEY_KPEQUAL"),
+    (sys::KEY_KPPLUSMINUS, "KEY_KPPLUSMINUS"),
+    (sys::KEY_PAUSE, "KEY_PAUSE"),
+    (sys::KEY_SCALE, "KEY_SCALE"),
+    (sys::KEY_KPCOMMA, "KEY_KPCOMMA"),
+    (sys::KEY_HANGEUL, "KEY_HANGEUL"),
+    (sys::KEY_HANJA, "KEY_HANJA"),
+    (sys::KEY_YEN, "KEY_YEN"),
+    (sys::KEY_LEFTMETA, "KEY_LEFTMETA"),
+    (sys::KEY_RIGHTMETA, "KEY_RIGHTMETA"),
+    (sys::KEY_COMPOSE, "KEY_COMPOSE"),
+    (sys::KEY_STOP, "KEY_STOP"),
+    (sys::KEY_AGAIN, "KEY_AGAIN"),
+    (sys::KEY_PROPS, "KEY_PROPS"),
+    (sys::KEY_UNDO, "KEY_UNDO"),
+    (sys::KEY_FRONT, "KEY_FRONT"),
+    (sys::KEY_COPY, "KEY_COPY"),
+    (sys::KEY_OPEN, "KEY_OPEN"),
+    (sys::KEY_PASTE, "KEY_PASTE"),
+    (sys::KEY_FIND, "KEY_FIND"),
+    (sys::KEY_CUT, "KEY_CUT"),
+    (sys::KEY_HELP, "KEY_HELP"),
+    (sys::KEY_MENU, "KEY_MENU"),
+    (sys::KEY_CALC, "KEY_CALC"),
+    (sys::KEY_SETUP, "KEY_SETUP"),
+    (sys::KEY_SLEEP, "KEY_SLEEP"),
+    (sys::KEY_WAKEUP, "KEY_WAKEUP"),
+    (sys::KEY_FILE, "KEY_FILE"),
+    (sys::KEY_SENDFILE, "KEY_SENDFILE"),
+    (sys::KEY_DELETEFILE, "KEY_DELETEFILE"),
+    (sys::KEY_XFER, "KEY_XFER"),
+    (sys::KEY_PROG1, "KEY_PROG1"),
+    (sys::KEY_PROG2, "KEY_PROG2"),
+    (sys::KEY_WWW, "KEY_WWW"),
+    (sys::KEY_MSDOS, "KEY_MSDOS"),
+    (sys::KEY_COFFEE, "KEY_COFFEE"),
+    (sys::KEY_ROTATE_DISPLAY, "KEY_ROTATE_DISPLAY"),
+    (sys::KEY_CYCLEWINDOWS, "KEY_CYCLEWINDOWS"),
+    (sys::KEY_MAIL, "KEY_MAIL"),
+    (sys::KEY_BOOKMARKS, "KEY_BOOKMARKS"),
+    (sys::KEY_COMPUTER, "KEY_COMPUTER"),
+    (sys::KEY_BACK, "KEY_BACK"),
+    (sys::KEY_FORWARD, "KEY_FORWARD"),
+    (sys::KEY_CLOSECD, "KEY_CLOSECD"),
+    (sys::KEY_EJECTCD, "KEY_EJECTCD"),
+    (sys::KEY_EJECTCLOSECD, "KEY_EJECTCLOSECD"),
+    (sys::KEY_NEXTSONG, "KEY_NEXTSONG"),
+    (sys::KEY_PLAYPAUSE, "KEY_PLAYPAUSE"),
+    (sys::KEY_PREVIOUSSONG, "KEY_PREVIOUSSONG"),
+    (sys::KEY_STOPCD, "KEY_STOPCD"),
+    (sys::KEY_RECORD, "KEY_RECORD"),
+    (sys::KEY_REWIND, "KEY_REWIND"),
+    (sys::KEY_PHONE, "KEY_PHONE"),
+    (sys::KEY_ISO, "KEY_ISO"),
+    (sys::KEY_CONFIG, "KEY_CONFIG"),
+    (sys::KEY_HOMEPAGE, "KEY_HOMEPAGE"),
+    (sys::KEY_REFRESH, "KEY_REFRESH"),
+    (sys::KEY_EXIT, "KEY_EXIT"),
+    (sys::KEY_MOVE, "KEY_MOVE"),
+    (sys::KEY_EDIT, "KEY_EDIT"),
+    (sys::KEY_SCROLLUP, "KEY_SCROLLUP"),
+    (sys::KEY_SCROLLDOWN, "KEY_SCROLLDOWN"),
+    (sys::KEY_KPLEFTPAREN, "KEY_KPLEFTPAREN"),
+    (sys::KEY_KPRIGHTPAREN, "KEY_KPRIGHTPAREN"),
+    (sys::KEY_NEW, "KEY_NEW"),
+    (sys::KEY_REDO, "KEY_REDO"),
+    (sys::KEY_F13, "KEY_F13"),
+    (sys::KEY_F14, "KEY_F14"),
+    (sys::KEY_F15, "KEY_F15"),
+    (sys::KEY_F16, "KEY_F16"),
+    (sys::KEY_F17, "KEY_F17"),
+    (sys::KEY_F18, "KEY_F18"),
+    (sys::KEY_F19, "KEY_F19"),
+    (sys::KEY_F20, "KEY_F20"),
+    (sys::KEY_F21, "KEY_F21"),
+    (sys::KEY_F22, "KEY_F22"),
+    (sys::KEY_F23, "KEY_F23"),
+    (sys::KEY_F24, "KEY_F24"),
+    (sys::KEY_PLAYCD, "KEY_PLAYCD"),
+    (sys::KEY_PAUSECD, "KEY_PAUSECD"),
+    (sys::KEY_PROG3, "KEY_PROG3"),
+    (sys::KEY_PROG4, "KEY_PROG4"),
+    (sys::KEY_ALL_APPLICATIONS, "KEY_ALL_APPLICATIONS"),
+    (sys::KEY_SUSPEND, "KEY_SUSPEND"),
+    (sys::KEY_CLOSE, "KEY_CLOSE"),
+    (sys::KEY_PLAY, "KEY_PLAY"),
+    (sys::KEY_FASTFORWARD, "KEY_FASTFORWARD"),
+    (sys::KEY_BASSBOOST, "KEY_BASSBOOST"),
+    (sys::KEY_PRINT, "KEY_PRINT"),
+    (sys::KEY_HP, "KEY_HP"),
+    (sys::KEY_CAMERA, "KEY_CAMERA"),
+    (sys::KEY_SOUND, "KEY_SOUND"),
+    (sys::KEY_QUESTION, "KEY_QUESTION"),
+    (sys::KEY_EMAIL, "KEY_EMAIL"),
+    (sys::KEY_CHAT, "KEY_CHAT"),
+    (sys::KEY_SEARCH, "KEY_SEARCH"),
+    (sys::KEY_CONNECT, "KEY_CONNECT"),
+    (sys::KEY_FINANCE, "KEY_FINANCE"),
+    (sys::KEY_SPORT, "KEY_SPORT"),
+    (sys::KEY_SHOP, "KEY_SHOP"),
+    (sys::KEY_ALTERASE, "KEY_ALTERASE"),
+    (sys::KEY_CANCEL, "KEY_CANCEL"),
+    (sys::KEY_BRIGHTNESSDOWN, "KEY_BRIGHTNESSDOWN"),
+    (sys::KEY_BRIGHTNESSUP, "KEY_BRIGHTNESSUP"),
+    (sys::KEY_MEDIA, "KEY_MEDIA"),
+    (sys::KEY_SWITCHVIDEOMODE, "KEY_SWITCHVIDEOMODE"),
+    (sys::KEY_KBDILLUMTOGGLE, "KEY_KBDILLUMTOGGLE"),
+    (sys::KEY_KBDILLUMDOWN, "KEY_KBDILLUMDOWN"),
+    (sys::KEY_KBDILLUMUP, "KEY_KBDILLUMUP"),
+    (sys::KEY_SEND, "KEY_SEND"),
+    (sys::KEY_REPLY, "KEY_REPLY"),
+    (sys::KEY_FORWARDMAIL, "KEY_FORWARDMAIL"),
+    (sys::KEY_SAVE, "KEY_SAVE"),
+    (sys::KEY_DOCUMENTS, "KEY_DOCUMENTS"),
+    (sys::KEY_BATTERY, "KEY_BATTERY"),
+    (sys::KEY_BLUETOOTH, "KEY_BLUETOOTH"),
+    (sys::KEY_WLAN, "KEY_WLAN"),
+    (sys::KEY_UWB, "KEY_UWB"),
+    (sys::KEY_UNKNOWN, "KEY_UNKNOWN"),
+    (sys::KEY_VIDEO_NEXT, "KEY_VIDEO_NEXT"),
+    (sys::KEY_VIDEO_PREV, "KEY_VIDEO_PREV"),
+    (sys::KEY_BRIGHTNESS_CYCLE, "KEY_BRIGHTNESS_CYCLE"),
+    (sys::KEY_BRIGHTNESS_AUTO, "KEY_BRIGHTNESS_AUTO"),
+    (sys::KEY_DISPLAY_OFF, "KEY_DISPLAY_OFF"),
+    (sys::KEY_WWAN, "KEY_WWAN"),
+    (sys::KEY_RFKILL, "KEY_RFKILL"),
+    (sys::KEY_MICMUTE, "KEY_MICMUTE"),
+    (sys::BTN_0, "BTN_0"),
+    (sys::BTN_1, "BTN_1"),
+    (sys::BTN_2, "BTN_2"),
+    (sys::BTN_3, "BTN_3"),
+    (sys::BTN_4, "BTN_4"),
+    (sys::BTN_5, "BTN_5"),
+    (sys::BTN_6, "BTN_6"),
+    (sys::BTN_7, "BTN_7"),
+    (sys::BTN_8, "BTN_8"),
+    (sys::BTN_9, "BTN_9"),
+    (sys::BTN_LEFT, "BTN_LEFT"),
+    (sys::BTN_RIGHT, "BTN_RIGHT"),
+    (sys::BTN_MIDDLE, "BTN_MIDDLE"),
+    (sys::BTN_SIDE, "BTN_SIDE"),
+    (sys::BTN_EXTRA, "BTN_EXTRA"),
+    (sys::BTN_FORWARD, "BTN_FORWARD"),
+    (sys::BTN_BACK, "BTN_BACK"),
+    (sys::BTN_TASK, "BTN_TASK"),
+    (sys::BTN_TRIGGER, "BTN_TRIGGER"),
+    (sys::BTN_THUMB, "BTN_THUMB"),
+    (sys::BTN_THUMB2, "BTN_THUMB2"),
+    (sys::BTN_TOP, "BTN_TOP"),
+    (sys::BTN_TOP2, "BTN_TOP2"),
+    (sys::BTN_PINKIE, "BTN_PINKIE"),
+    (sys::BTN_BASE, "BTN_BASE"),
+    (sys::BTN_BASE2, "BTN_BASE2"),
+    (sys::BTN_BASE3, "BTN_BASE3"),
+    (sys::BTN_BASE4, "BTN_BASE4"),
+    (sys::BTN_BASE5, "BTN_BASE5"),
+    (sys::BTN_BASE6, "BTN_BASE6"),
+    (sys::BTN_DEAD, "BTN_DEAD"),
+    (sys::BTN_SOUTH, "BTN_SOUTH"),
+    (sys::BTN_EAST, "BTN_EAST"),
+    (sys::BTN_C, "BTN_C"),
+    (sys::BTN_NORTH, "BTN_NORTH"),
+    (sys::BTN_WEST, "BTN_WEST"),
+    (sys::BTN_Z, "BTN_Z"),
+    (sys::BTN_TL, "BTN_TL"),
+    (sys::BTN_TR, "BTN_TR"),
+    (sys::BTN_TL2, "BTN_TL2"),
+    (sys::BTN_TR2, "BTN_TR2"),
+    (sys::BTN_SELECT, "BTN_SELECT"),
+    (sys::BTN_START, "BTN_START"),
+    (sys::BTN_MODE, "BTN_MODE"),
+    (sys::BTN_THUMBL, "BTN_THUMBL"),
+    (sys::BTN_THUMBR, "BTN_THUMBR"),
+    (sys::BTN_TOOL_PEN, "BTN_TOOL_PEN"),
+    (sys::BTN_TOOL_RUBBER, "BTN_TOOL_RUBBER"),
+    (sys::BTN_TOOL_BRUSH, "BTN_TOOL_BRUSH"),
+    (sys::BTN_TOOL_PENCIL, "BTN_TOOL_PENCIL"),
+    (sys::BTN_TOOL_AIRBRUSH, "BTN_TOOL_AIRBRUSH"),
+    (sys::BTN_TOOL_FINGER, "BTN_TOOL_FINGER"),
+    (sys::BTN_TOOL_MOUSE, "BTN_TOOL_MOUSE"),
+    (sys::BTN_TOOL_LENS, "BTN_TOOL_LENS"),
+    (sys::BTN_TOOL_QUINTTAP, "BTN_TOOL_QUINTTAP"),
+    (sys::BTN_STYLUS3, "BTN_STYLUS3"),
+    (sys::BTN_TOUCH, "BTN_TOUCH"),
+    (sys::BTN_STYLUS, "BTN_STYLUS"),
+    (sys::BTN_STYLUS2, "BTN_STYLUS2"),
+    (sys::BTN_TOOL_DOUBLETAP, "BTN_TOOL_DOUBLETAP"),
+    (sys::BTN_TOOL_TRIPLETAP, "BTN_TOOL_TRIPLETAP"),
+    (sys::BTN_TOOL_QUADTAP, "BTN_TOOL_QUADTAP"),
+    (sys::BTN_GEAR_DOWN, "BTN_GEAR_DOWN"),
+    (sys::BTN_GEAR_UP, "BTN_GEAR_UP"),
+    (sys::KEY_OK, "KEY_OK"),
+    (sys::KEY_SELECT, "KEY_SELECT"),
+    (sys::KEY_GOTO, "KEY_GOTO"),
+    (sys::KEY_CLEAR, "KEY_CLEAR"),
+    (sys::KEY_POWER2, "KEY_POWER2"),
+    (sys::KEY_OPTION, "KEY_OPTION"),
+    (sys::KEY_INFO, "KEY_INFO"),
+    (sys::KEY_TIME, "KEY_TIME"),
+    (sys::KEY_VENDOR, "KEY_VENDOR"),
+    (sys::KEY_ARCHIVE, "KEY_ARCHIVE"),
+    (sys::KEY_PROGRAM, "KEY_PROGRAM"),
+    (sys::KEY_CHANNEL, "KEY_CHANNEL"),
+    (sys::KEY_FAVORITES, "KEY_FAVORITES"),
+    (sys::KEY_EPG, "KEY_EPG"),
+    (sys::KEY_PVR, "KEY_PVR"),
+    (sys::KEY_MHP, "KEY_MHP"),
+    (sys::KEY_LANGUAGE, "KEY_LANGUAGE"),
+    (sys::KEY_TITLE, "KEY_TITLE"),
+    (sys::KEY_SUBTITLE, "KEY_SUBTITLE"),
+    (sys::KEY_ANGLE, "KEY_ANGLE"),
+    (sys::KEY_FULL_SCREEN, "KEY_FULL_SCREEN"),
+    (sys::KEY_MODE, "KEY_MODE"),
+    (sys::KEY_KEYBOARD, "KEY_KEYBOARD"),
+    (sys::KEY_ASPECT_RATIO, "KEY_ASPECT_RATIO"),
+    (sys::KEY_PC, "KEY_PC"),
+    (sys::KEY_TV, "KEY_TV"),
+    (sys::KEY_TV2, "KEY_TV2"),
+    (sys::KEY_VCR, "KEY_VCR"),
+    (sys::KEY_VCR2, "KEY_VCR2"),
+    (sys::KEY_SAT, "KEY_SAT"),
+    (sys::KEY_SAT2, "KEY_SAT2"),
+    (sys::KEY_CD, "KEY_CD"),
+    (sys::KEY_TAPE, "KEY_TAPE"),
+    (sys::KEY_RADIO, "KEY_RADIO"),
+    (sys::KEY_TUNER, "KEY_TUNER"),
+    (sys::KEY_PLAYER, "KEY_PLAYER"),
+    (sys::KEY_TEXT, "KEY_TEXT"),
+    (sys::KEY_DVD, "KEY_DVD"),
+    (sys::KEY_AUX, "KEY_AUX"),
+    (sys::KEY_MP3, "KEY_MP3"),
+    (sys::KEY_AUDIO, "KEY_AUDIO"),
+    (sys::KEY_VIDEO, "KEY_VIDEO"),
+    (sys::KEY_DIRECTORY, "KEY_DIRECTORY"),
+    (sys::KEY_LIST, "KEY_LIST"),
+    (sys::KEY_MEMO, "KEY_MEMO"),
+    (sys::KEY_CALENDAR, "KEY_CALENDAR"),
+    (sys::KEY_RED, "KEY_RED"),
+    (sys::KEY_GREEN, "KEY_GREEN"),
+    (sys::KEY_YELLOW, "KEY_YELLOW"),
+    (sys::KEY_BLUE, "KEY_BLUE"),
+    (sys::KEY_CHANNELUP, "KEY_CHANNELUP"),
+    (sys::KEY_CHANNELDOWN, "KEY_CHANNELDOWN"),
+    (sys::KEY_FIRST, "KEY_FIRST"),
+    (sys::KEY_LAST, "KEY_LAST"),
+    (sys::KEY_AB, "KEY_AB"),
+    (sys::KEY_NEXT, "KEY_NEXT"),
+    (sys::KEY_RESTART, "KEY_RESTART"),
+    (sys::KEY_SLOW, "KEY_SLOW"),
+    (sys::KEY_SHUFFLE, "KEY_SHUFFLE"),
+    (sys::KEY_BREAK, "KEY_BREAK"),
+    (sys::KEY_PREVIOUS, "KEY_PREVIOUS"),
+    (sys::KEY_DIGITS, "KEY_DIGITS"),
+    (sys::KEY_TEEN, "KEY_TEEN"),
+    (sys::KEY_TWEN, "KEY_TWEN"),
+    (sys::KEY_VIDEOPHONE, "KEY_VIDEOPHONE"),
+    (sys::KEY_GAMES, "KEY_GAMES"),
+    (sys::KEY_ZOOMIN, "KEY_ZOOMIN"),
+    (sys::KEY_ZOOMOUT, "KEY_ZOOMOUT"),
+    (sys::KEY_ZOOMRESET, "KEY_ZOOMRESET"),
+    (sys::KEY_WORDPROCESSOR, "KEY_WORDPROCESSOR"),
+    (sys::KEY_EDITOR, "KEY_EDITOR"),
+    (sys::KEY_SPREADSHEET, "KEY_SPREADSHEET"),
+    (sys::KEY_GRAPHICSEDITOR, "KEY_GRAPHICSEDITOR"),
+    (sys::KEY_PRESENTATION, "KEY_PRESENTATION"),
+    (sys::KEY_DATABASE, "KEY_DATABASE"),
+    (sys::KEY_NEWS, "KEY_NEWS"),
+    (sys::KEY_VOICEMAIL, "KEY_VOICEMAIL"),
+    (sys::KEY_ADDRESSBOOK, "KEY_ADDRESSBOOK"),
+    (sys::KEY_MESSENGER, "KEY_MESSENGER"),
+    (sys::KEY_DISPLAYTOGGLE, "KEY_DISPLAYTOGGLE"),
+    (sys::KEY_SPELLCHECK, "KEY_SPELLCHECK"),
+    (sys::KEY_LOGOFF, "KEY_LOGOFF"),
+    (sys::KEY_DOLLAR, "KEY_DOLLAR"),
+    (sys::KEY_EURO, "KEY_EURO"),
+    (sys::KEY_FRAMEBACK, "KEY_FRAMEBACK"),
+    (sys::KEY_FRAMEFORWARD, "KEY_FRAMEFORWARD"),
+    (sys::KEY_CONTEXT_MENU, "KEY_CONTEXT_MENU"),
+    (sys::KEY_MEDIA_REPEAT, "KEY_MEDIA_REPEAT"),
+    (sys::KEY_10CHANNELSUP, "KEY_10CHANNELSUP"),
+    (sys::KEY_10CHANNELSDOWN, "KEY_10CHANNELSDOWN"),
+    (sys::KEY_IMAGES, "KEY_IMAGES"),
+    (sys::KEY_NOTIFICATION_CENTER, "KEY_NOTIFICATION_CENTER"),
+    (sys::KEY_PICKUP_PHONE, "KEY_PICKUP_PHONE"),
+    (sys::KEY_HANGUP_PHONE, "KEY_HANGUP_PHONE"),
+    (sys::KEY_DEL_EOL, "KEY_DEL_EOL"),
+    (sys::KEY_DEL_EOS, "KEY_DEL_EOS"),
+    (sys::KEY_INS_LINE, "KEY_INS_LINE"),
+    (sys::KEY_DEL_LINE, "KEY_DEL_LINE"),
+    (sys::KEY_FN, "KEY_FN"),
+    (sys::KEY_FN_ESC, "KEY_FN_ESC"),
+    (sys::KEY_FN_F1, "KEY_FN_F1"),
+    (sys::KEY_FN_F2, "KEY_FN_F2"),
+    (sys::KEY_FN_F3, "KEY_FN_F3"),
+    (sys::KEY_FN_F4, "KEY_FN_F4"),
+    (sys::KEY_FN_F5, "KEY_FN_F5"),
+    (sys::KEY_FN_F6, "KEY_FN_F6"),
+    (sys::KEY_FN_F7, "KEY_FN_F7"),
+    (sys::KEY_FN_F8, "KEY_FN_F8"),
+    (sys::KEY_FN_F9, "KEY_FN_F9"),
+    (sys::KEY_FN_F10, "KEY_FN_F10"),
+    (sys::KEY_FN_F11, "KEY_FN_F11"),
+    (sys::KEY_FN_F12, "KEY_FN_F12"),
+    (sys::KEY_FN_1, "KEY_FN_1"),
+    (sys::KEY_FN_2, "KEY_FN_2"),
+    (sys::KEY_FN_D, "KEY_FN_D"),
+    (sys::KEY_FN_E, "KEY_FN_E"),
+    (sys::KEY_FN_F, "KEY_FN_F"),
+    (sys::KEY_FN_S, "KEY_FN_S"),
+    (sys::KEY_FN_B, "KEY_FN_B"),
+    (sys::KEY_FN_RIGHT_SHIFT, "KEY_FN_RIGHT_SHIFT"),
+    (sys::KEY_BRL_DOT1, "KEY_BRL_DOT1"),
+    (sys::KEY_BRL_DOT2, "KEY_BRL_DOT2"),
+    (sys::KEY_BRL_DOT3, "KEY_BRL_DOT3"),
+    (sys::KEY_BRL_DOT4, "KEY_BRL_DOT4"),
+    (sys::KEY_BRL_DOT5, "KEY_BRL_DOT5"),
+    (sys::KEY_BRL_DOT6, "KEY_BRL_DOT6"),
+    (sys::KEY_BRL_DOT7, "KEY_BRL_DOT7"),
+    (sys::KEY_BRL_DOT8, "KEY_BRL_DOT8"),
+    (sys::KEY_BRL_DOT9, "KEY_BRL_DOT9"),
+    (sys::KEY_BRL_DOT10, "KEY_BRL_DOT10"),
+    (sys::KEY_NUMERIC_0, "KEY_NUMERIC_0"),
+    (sys::KEY_NUMERIC_1, "KEY_NUMERIC_1"),
+    (sys::KEY_NUMERIC_2, "KEY_NUMERIC_2"),
+    (sys::KEY_NUMERIC_3, "KEY_NUMERIC_3"),
+    (sys::KEY_NUMERIC_4, "KEY_NUMERIC_4"),
+    (sys::KEY_NUMERIC_5, "KEY_NUMERIC_5"),
+    (sys::KEY_NUMERIC_6, "KEY_NUMERIC_6"),
+    (sys::KEY_NUMERIC_7, "KEY_NUMERIC_7"),
+    (sys::KEY_NUMERIC_8, "KEY_NUMERIC_8"),
+    (sys::KEY_NUMERIC_9, "KEY_NUMERIC_9"),
+    (sys::KEY_NUMERIC_STAR, "KEY_NUMERIC_STAR"),
+    (sys::KEY_NUMERIC_POUND, "KEY_NUMERIC_POUND"),
+    (sys::KEY_NUMERIC_A, "KEY_NUMERIC_A"),
+    (sys::KEY_NUMERIC_B, "KEY_NUMERIC_B"),
+    (sys::KEY_NUMERIC_C, "KEY_NUMERIC_C"),
+    (sys::KEY_NUMERIC_D, "KEY_NUMERIC_D"),
+    (sys::KEY_CAMERA_FOCUS, "KEY_CAMERA_FOCUS"),
+    (sys::KEY_WPS_BUTTON, "KEY_WPS_BUTTON"),
+    (sys::KEY_TOUCHPAD_TOGGLE, "KEY_TOUCHPAD_TOGGLE"),
+    (sys::KEY_TOUCHPAD_ON, "KEY_TOUCHPAD_ON"),
+    (sys::KEY_TOUCHPAD_OFF, "KEY_TOUCHPAD_OFF"),
+    (sys::KEY_CAMERA_ZOOMIN, "KEY_CAMERA_ZOOMIN"),
+    (sys::KEY_CAMERA_ZOOMOUT, "KEY_CAMERA_ZOOMOUT"),
+    (sys::KEY_CAMERA_UP, "KEY_CAMERA_UP"),
+    (sys::KEY_CAMERA_DOWN, "KEY_CAMERA_DOWN"),
+    (sys::KEY_CAMERA_LEFT, "KEY_CAMERA_LEFT"),
+    (sys::KEY_CAMERA_RIGHT, "KEY_CAMERA_RIGHT"),
+    (sys::KEY_ATTENDANT_ON, "KEY_ATTENDANT_ON"),
+    (sys::KEY_ATTENDANT_OFF, "KEY_ATTENDANT_OFF"),
+    (sys::KEY_ATTENDANT_TOGGLE, "KEY_ATTENDANT_TOGGLE"),
+    (sys::KEY_LIGHTS_TOGGLE, "KEY_LIGHTS_TOGGLE"),
+    (sys::BTN_DPAD_UP, "BTN_DPAD_UP"),
+    (sys::BTN_DPAD_DOWN, "BTN_DPAD_DOWN"),
+    (sys::BTN_DPAD_LEFT, "BTN_DPAD_LEFT"),
+    (sys::BTN_DPAD_RIGHT, "BTN_DPAD_RIGHT"),
+    (sys::KEY_ALS_TOGGLE, "KEY_ALS_TOGGLE"),
+    (sys::KEY_ROTATE_LOCK_TOGGLE, "KEY_ROTATE_LOCK_TOGGLE"),
+    (sys::KEY_REFRESH_RATE_TOGGLE, "KEY_REFRESH_RATE_TOGGLE"),
+    (sys::KEY_BUTTONCONFIG, "KEY_BUTTONCONFIG"),
+    (sys::KEY_TASKMANAGER, "KEY_TASKMANAGER"),
+    (sys::KEY_JOURNAL, "KEY_JOURNAL"),
+    (sys::KEY_CONTROLPANEL, "KEY_CONTROLPANEL"),
+    (sys::KEY_APPSELECT, "KEY_APPSELECT"),
+    (sys::KEY_SCREENSAVER, "KEY_SCREENSAVER"),
+    (sys::KEY_VOICECOMMAND, "KEY_VOICECOMMAND"),
+    (sys::KEY_ASSISTANT, "KEY_ASSISTANT"),
+    (sys::KEY_KBD_LAYOUT_NEXT, "KEY_KBD_LAYOUT_NEXT"),
+    (sys::KEY_EMOJI_PICKER, "KEY_EMOJI_PICKER"),
+    (sys::KEY_DICTATE, "KEY_DICTATE"),
+    (sys::KEY_CAMERA_ACCESS_ENABLE, "KEY_CAMERA_ACCESS_ENABLE"),
+    (sys::KEY_CAMERA_ACCESS_DISABLE, "KEY_CAMERA_ACCESS_DISABLE"),
+    (sys::KEY_CAMERA_ACCESS_TOGGLE, "KEY_CAMERA_ACCESS_TOGGLE"),
+    (sys::KEY_ACCESSIBILITY, "KEY_ACCESSIBILITY"),
+    (sys::KEY_DO_NOT_DISTURB, "KEY_DO_NOT_DISTURB"),
+    (sys::KEY_BRIGHTNESS_MIN, "KEY_BRIGHTNESS_MIN"),
+    (sys::KEY_KBDINPUTASSIST_PREV, "KEY_KBDINPUTASSIST_PREV"),
+    (sys::KEY_KBDINPUTASSIST_NEXT, "KEY_KBDINPUTASSIST_NEXT"),
+    (
+        sys::KEY_KBDINPUTASSIST_PREVGROUP,
+        "KEY_KBDINPUTASSIST_PREVGROUP",
+    ),
+    (
+        sys::KEY_KBDINPUTASSIST_NEXTGROUP,
+        "KEY_KBDINPUTASSIST_NEXTGROUP",
+    ),
+    (sys::KEY_KBDINPUTASSIST_ACCEPT, "KEY_KBDINPUTASSIST_ACCEPT"),
+    (sys::KEY_KBDINPUTASSIST_CANCEL, "KEY_KBDINPUTASSIST_CANCEL"),
+    (sys::KEY_RIGHT_UP, "KEY_RIGHT_UP"),
+    (sys::KEY_RIGHT_DOWN, "KEY_RIGHT_DOWN"),
+    (sys::KEY_LEFT_UP, "KEY_LEFT_UP"),
+    (sys::KEY_LEFT_DOWN, "KEY_LEFT_DOWN"),
+    (sys::KEY_ROOT_MENU, "KEY_ROOT_MENU"),
+    (sys::KEY_MEDIA_TOP_MENU, "KEY_MEDIA_TOP_MENU"),
+    (sys::KEY_NUMERIC_11, "KEY_NUMERIC_11"),
+    (sys::KEY_NUMERIC_12, "KEY_NUMERIC_12"),
+    (sys::KEY_AUDIO_DESC, "KEY_AUDIO_DESC"),
+    (sys::KEY_3D_MODE, "KEY_3D_MODE"),
+    (sys::KEY_NEXT_FAVORITE, "KEY_NEXT_FAVORITE"),
+    (sys::KEY_STOP_RECORD, "KEY_STOP_RECORD"),
+    (sys::KEY_PAUSE_RECORD, "KEY_PAUSE_RECORD"),
+    (sys::KEY_VOD, "KEY_VOD"),
+    (sys::KEY_UNMUTE, "KEY_UNMUTE"),
+    (sys::KEY_FASTREVERSE, "KEY_FASTREVERSE"),
+    (sys::KEY_SLOWREVERSE, "KEY_SLOWREVERSE"),
+    (sys::KEY_DATA, "KEY_DATA"),
+    (sys::KEY_ONSCREEN_KEYBOARD, "KEY_ONSCREEN_KEYBOARD"),
+    (sys::KEY_PRIVACY_SCREEN_TOGGLE, "KEY_PRIVACY_SCREEN_TOGGLE"),
+    (sys::KEY_SELECTIVE_SCREENSHOT, "KEY_SELECTIVE_SCREENSHOT"),
+    (sys::KEY_NEXT_ELEMENT, "KEY_NEXT_ELEMENT"),
+    (sys::KEY_PREVIOUS_ELEMENT, "KEY_PREVIOUS_ELEMENT"),
+    (
+        sys::KEY_AUTOPILOT_ENGAGE_TOGGLE,
+        "KEY_AUTOPILOT_ENGAGE_TOGGLE",
+    ),
+    (sys::KEY_MARK_WAYPOINT, "KEY_MARK_WAYPOINT"),
+    (sys::KEY_SOS, "KEY_SOS"),
+    (sys::KEY_NAV_CHART, "KEY_NAV_CHART"),
+    (sys::KEY_FISHING_CHART, "KEY_FISHING_CHART"),
+    (sys::KEY_SINGLE_RANGE_RADAR, "KEY_SINGLE_RANGE_RADAR"),
+    (sys::KEY_DUAL_RANGE_RADAR, "KEY_DUAL_RANGE_RADAR"),
+    (sys::KEY_RADAR_OVERLAY, "KEY_RADAR_OVERLAY"),
+    (sys::KEY_TRADITIONAL_SONAR, "KEY_TRADITIONAL_SONAR"),
+    (sys::KEY_CLEARVU_SONAR, "KEY_CLEARVU_SONAR"),
+    (sys::KEY_SIDEVU_SONAR, "KEY_SIDEVU_SONAR"),
+    (sys::KEY_NAV_INFO, "KEY_NAV_INFO"),
+    (sys::KEY_BRIGHTNESS_MENU, "KEY_BRIGHTNESS_MENU"),
+    (sys::KEY_MACRO1, "KEY_MACRO1"),
+    (sys::KEY_MACRO2, "KEY_MACRO2"),
+    (sys::KEY_MACRO3, "KEY_MACRO3"),
+    (sys::KEY_MACRO4, "KEY_MACRO4"),
+    (sys::KEY_MACRO5, "KEY_MACRO5"),
+    (sys::KEY_MACRO6, "KEY_MACRO6"),
+    (sys::KEY_MACRO7, "KEY_MACRO7"),
+    (sys::KEY_MACRO8, "KEY_MACRO8"),
+    (sys::KEY_MACRO9, "KEY_MACRO9"),
+    (sys::KEY_MACRO10, "KEY_MACRO10"),
+    (sys::KEY_MACRO11, "KEY_MACRO11"),
+    (sys::KEY_MACRO12, "KEY_MACRO12"),
+    (sys::KEY_MACRO13, "KEY_MACRO13"),
+    (sys::KEY_MACRO14, "KEY_MACRO14"),
+    (sys::KEY_MACRO15, "KEY_MACRO15"),
+    (sys::KEY_MACRO16, "KEY_MACRO16"),
+    (sys::KEY_MACRO17, "KEY_MACRO17"),
+    (sys::KEY_MACRO18, "KEY_MACRO18"),
+    (sys::KEY_MACRO19, "KEY_MACRO19"),
+    (sys::KEY_MACRO20, "KEY_MACRO20"),
+    (sys::KEY_MACRO21, "KEY_MACRO21"),
+    (sys::KEY_MACRO22, "KEY_MACRO22"),
+    (sys::KEY_MACRO23, "KEY_MACRO23"),
+    (sys::KEY_MACRO24, "KEY_MACRO24"),
+    (sys::KEY_MACRO25, "KEY_MACRO25"),
+    (sys::KEY_MACRO26, "KEY_MACRO26"),
+    (sys::KEY_MACRO27, "KEY_MACRO27"),
+    (sys::KEY_MACRO28, "KEY_MACRO28"),
+    (sys::KEY_MACRO29, "KEY_MACRO29"),
+    (sys::KEY_MACRO30, "KEY_MACRO30"),
+    (sys::KEY_MACRO_RECORD_START, "KEY_MACRO_RECORD_START"),
+    (sys::KEY_MACRO_RECORD_STOP, "KEY_MACRO_RECORD_STOP"),
+    (sys::KEY_MACRO_PRESET_CYCLE, "KEY_MACRO_PRESET_CYCLE"),
+    (sys::KEY_MACRO_PRESET1, "KEY_MACRO_PRESET1"),
+    (sys::KEY_MACRO_PRESET2, "KEY_MACRO_PRESET2"),
+    (sys::KEY_MACRO_PRESET3, "KEY_MACRO_PRESET3"),
+    (sys::KEY_KBD_LCD_MENU1, "KEY_KBD_LCD_MENU1"),
+    (sys::KEY_KBD_LCD_MENU2, "KEY_KBD_LCD_MENU2"),
+    (sys::KEY_KBD_LCD_MENU3, "KEY_KBD_LCD_MENU3"),
+    (sys::KEY_KBD_LCD_MENU4, "KEY_KBD_LCD_MENU4"),
+    (sys::KEY_KBD_LCD_MENU5, "KEY_KBD_LCD_MENU5"),
+    (sys::BTN_TRIGGER_HAPPY1, "BTN_TRIGGER_HAPPY1"),
+    (sys::BTN_TRIGGER_HAPPY2, "BTN_TRIGGER_HAPPY2"),
+    (sys::BTN_TRIGGER_HAPPY3, "BTN_TRIGGER_HAPPY3"),
+    (sys::BTN_TRIGGER_HAPPY4, "BTN_TRIGGER_HAPPY4"),
+    (sys::BTN_TRIGGER_HAPPY5, "BTN_TRIGGER_HAPPY5"),
+    (sys::BTN_TRIGGER_HAPPY6, "BTN_TRIGGER_HAPPY6"),
+    (sys::BTN_TRIGGER_HAPPY7, "BTN_TRIGGER_HAPPY7"),
+    (sys::BTN_TRIGGER_HAPPY8, "BTN_TRIGGER_HAPPY8"),
+    (sys::BTN_TRIGGER_HAPPY9, "BTN_TRIGGER_HAPPY9"),
+    (sys::BTN_TRIGGER_HAPPY10, "BTN_TRIGGER_HAPPY10"),
+    (sys::BTN_TRIGGER_HAPPY11, "BTN_TRIGGER_HAPPY11"),
+    (sys::BTN_TRIGGER_HAPPY12, "BTN_TRIGGER_HAPPY12"),
+    (sys::BTN_TRIGGER_HAPPY13, "BTN_TRIGGER_HAPPY13"),
+    (sys::BTN_TRIGGER_HAPPY14, "BTN_TRIGGER_HAPPY14"),
+    (sys::BTN_TRIGGER_HAPPY15, "BTN_TRIGGER_HAPPY15"),
+    (sys::BTN_TRIGGER_HAPPY16, "BTN_TRIGGER_HAPPY16"),
+    (sys::BTN_TRIGGER_HAPPY17, "BTN_TRIGGER_HAPPY17"),
+    (sys::BTN_TRIGGER_HAPPY18, "BTN_TRIGGER_HAPPY18"),
+    (sys::BTN_TRIGGER_HAPPY19, "BTN_TRIGGER_HAPPY19"),
+    (sys::BTN_TRIGGER_HAPPY20, "BTN_TRIGGER_HAPPY20"),
+    (sys::BTN_TRIGGER_HAPPY21, "BTN_TRIGGER_HAPPY21"),
+    (sys::BTN_TRIGGER_HAPPY22, "BTN_TRIGGER_HAPPY22"),
+    (sys::BTN_TRIGGER_HAPPY23, "BTN_TRIGGER_HAPPY23"),
+    (sys::BTN_TRIGGER_HAPPY24, "BTN_TRIGGER_HAPPY24"),
+    (sys::BTN_TRIGGER_HAPPY25, "BTN_TRIGGER_HAPPY25"),
+    (sys::BTN_TRIGGER_HAPPY26, "BTN_TRIGGER_HAPPY26"),
+    (sys::BTN_TRIGGER_HAPPY27, "BTN_TRIGGER_HAPPY27"),
+    (sys::BTN_TRIGGER_HAPPY28, "BTN_TRIGGER_HAPPY28"),
+    (sys::BTN_TRIGGER_HAPPY29, "BTN_TRIGGER_HAPPY29"),
+    (sys::BTN_TRIGGER_HAPPY30, "BTN_TRIGGER_HAPPY30"),
+    (sys::BTN_TRIGGER_HAPPY31, "BTN_TRIGGER_HAPPY31"),
+    (sys::BTN_TRIGGER_HAPPY32, "BTN_TRIGGER_HAPPY32"),
+    (sys::BTN_TRIGGER_HAPPY33, "BTN_TRIGGER_HAPPY33"),
+    (sys::BTN_TRIGGER_HAPPY34, "BTN_TRIGGER_HAPPY34"),
+    (sys::BTN_TRIGGER_HAPPY35, "BTN_TRIGGER_HAPPY35"),
+    (sys::BTN_TRIGGER_HAPPY36, "BTN_TRIGGER_HAPPY36"),
+    (sys::BTN_TRIGGER_HAPPY37, "BTN_TRIGGER_HAPPY37"),
+    (sys::BTN_TRIGGER_HAPPY38, "BTN_TRIGGER_HAPPY38"),
+    (sys::BTN_TRIGGER_HAPPY39, "BTN_TRIGGER_HAPPY39"),
+    (sys::BTN_TRIGGER_HAPPY40, "BTN_TRIGGER_HAPPY40"),
+];
+
+const REL: &[(i32, &str)] = &[
+    (sys::REL_X, "REL_X"),
+    (sys::REL_Y, "REL_Y"),
+    (sys::REL_Z, "REL_Z"),
+    (sys::REL_RX, "REL_RX"),
+    (sys::REL_RY, "REL_RY"),
+    (sys::REL_RZ, "REL_RZ"),
+    (sys::REL_HWHEEL, "REL_HWHEEL"),
+    (sys::REL_DIAL, "REL_DIAL"),
+    (sys::REL_WHEEL, "REL_WHEEL"),
+    (sys::REL_MISC, "REL_MISC"),
+    (sys::REL_RESERVED, "REL_RESERVED"),
+    (sys::REL_WHEEL_HI_RES, "REL_WHEEL_HI_RES"),
+    (sys::REL_HWHEEL_HI_RES, "REL_HWHEEL_HI_RES"),
+];
+
+const ABS: &[(i32, &str)] = &[
+    (sys::ABS_X, "ABS_X"),
+    (sys::ABS_Y, "ABS_Y"),
+    (sys::ABS_Z, "ABS_Z"),
+    (sys::ABS_RX, "ABS_RX"),
+    (sys::ABS_RY, "ABS_RY"),
+    (sys::ABS_RZ, "ABS_RZ"),
+    (sys::ABS_THROTTLE, "ABS_THROTTLE"),
+    (sys::ABS_RUDDER, "ABS_RUDDER"),
+    (sys::ABS_WHEEL, "ABS_WHEEL"),
+    (sys::ABS_GAS, "ABS_GAS"),
+    (sys::ABS_BRAKE, "ABS_BRAKE"),
+    (sys::ABS_HAT0X, "ABS_HAT0X"),
+    (sys::ABS_HAT0Y, "ABS_HAT0Y"),
+    (sys::ABS_HAT1X, "ABS_HAT1X"),
+    (sys::ABS_HAT1Y, "ABS_HAT1Y"),
+    (sys::ABS_HAT2X, "ABS_HAT2X"),
+    (sys::ABS_HAT2Y, "ABS_HAT2Y"),
+    (sys::ABS_HAT3X, "ABS_HAT3X"),
+    (sys::ABS_HAT3Y, "ABS_HAT3Y"),
+    (sys::ABS_PRESSURE, "ABS_PRESSURE"),
+    (sys::ABS_DISTANCE, "ABS_DISTANCE"),
+    (sys::ABS_TILT_X, "ABS_TILT_X"),
+    (sys::ABS_TILT_Y, "ABS_TILT_Y"),
+    (sys::ABS_TOOL_WIDTH, "ABS_TOOL_WIDTH"),
+    (sys::ABS_VOLUME, "ABS_VOLUME"),
+    (sys::ABS_PROFILE, "ABS_PROFILE"),
+    (sys::ABS_MISC, "ABS_MISC"),
+    (sys::ABS_RESERVED, "ABS_RESERVED"),
+    (sys::ABS_MT_SLOT, "ABS_MT_SLOT"),
+    (sys::ABS_MT_TOUCH_MAJOR, "ABS_MT_TOUCH_MAJOR"),
+    (sys::ABS_MT_TOUCH_MINOR, "ABS_MT_TOUCH_MINOR"),
+    (sys::ABS_MT_WIDTH_MAJOR, "ABS_MT_WIDTH_MAJOR"),
+    (sys::ABS_MT_WIDTH_MINOR, "ABS_MT_WIDTH_MINOR"),
+    (sys::ABS_MT_ORIENTATION, "ABS_MT_ORIENTATION"),
+    (sys::ABS_MT_POSITION_X, "ABS_MT_POSITION_X"),
+    (sys::ABS_MT_POSITION_Y, "ABS_MT_POSITION_Y"),
+    (sys::ABS_MT_TOOL_TYPE, "ABS_MT_TOOL_TYPE"),
+    (sys::ABS_MT_BLOB_ID, "ABS_MT_BLOB_ID"),
+    (sys::ABS_MT_TRACKING_ID, "ABS_MT_TRACKING_ID"),
+    (sys::ABS_MT_PRESSURE, "ABS_MT_PRESSURE"),
+    (sys::ABS_MT_DISTANCE, "ABS_MT_DISTANCE"),
+    (sys::ABS_MT_TOOL_X, "ABS_MT_TOOL_X"),
+    (sys::ABS_MT_TOOL_Y, "ABS_MT_TOOL_Y"),
+];
+
+const MSC: &[(i32, &str)] = &[
+    (sys::MSC_SERIAL, "MSC_SERIAL"),
+    (sys::MSC_PULSELED, "MSC_PULSELED"),
+    (sys::MSC_GESTURE, "MSC_GESTURE"),
+    (sys::MSC_RAW, "MSC_RAW"),
+    (sys::MSC_SCAN, "MSC_SCAN"),
+    (sys::MSC_TIMESTAMP, "MSC_TIMESTAMP"),
+];
+
+const SW: &[(i32, &str)] = &[
+    (sys::SW_LID, "SW_LID"),
+    (sys::SW_TABLET_MODE, "SW_TABLET_MODE"),
+    (sys::SW_HEADPHONE_INSERT, "SW_HEADPHONE_INSERT"),
+    (sys::SW_RFKILL_ALL, "SW_RFKILL_ALL"),
+    (sys::SW_MICROPHONE_INSERT, "SW_MICROPHONE_INSERT"),
+    (sys::SW_DOCK, "SW_DOCK"),
+    (sys::SW_LINEOUT_INSERT, "SW_LINEOUT_INSERT"),
+    (sys::SW_JACK_PHYSICAL_INSERT, "SW_JACK_PHYSICAL_INSERT"),
+    (sys::SW_VIDEOOUT_INSERT, "SW_VIDEOOUT_INSERT"),
+    (sys::SW_CAMERA_LENS_COVER, "SW_CAMERA_LENS_COVER"),
+    (sys::SW_KEYPAD_SLIDE, "SW_KEYPAD_SLIDE"),
+    (sys::SW_FRONT_PROXIMITY, "SW_FRONT_PROXIMITY"),
+    (sys::SW_ROTATE_LOCK, "SW_ROTATE_LOCK"),
+    (sys::SW_LINEIN_INSERT, "SW_LINEIN_INSERT"),
+    (sys::SW_MUTE_DEVICE, "SW_MUTE_DEVICE"),
+    (sys::SW_PEN_INSERTED, "SW_PEN_INSERTED"),
+    (sys::SW_MACHINE_COVER, "SW_MACHINE_COVER"),
+];
+
+const LED: &[(i32, &str)] = &[
+    (sys::LED_NUML, "LED_NUML"),
+    (sys::LED_CAPSL, "LED_CAPSL"),
+    (sys::LED_SCROLLL, "LED_SCROLLL"),
+    (sys::LED_COMPOSE, "LED_COMPOSE"),
+    (sys::LED_KANA, "LED_KANA"),
+    (sys::LED_SLEEP, "LED_SLEEP"),
+    (sys::LED_SUSPEND, "LED_SUSPEND"),
+    (sys::LED_MUTE, "LED_MUTE"),
+    (sys::LED_MISC, "LED_MISC"),
+    (sys::LED_MAIL, "LED_MAIL"),
+    (sys::LED_CHARGING, "LED_CHARGING"),
+];
+
+const SND: &[(i32, &str)] = &[
+    (sys::SND_CLICK, "SND_CLICK"),
+    (sys::SND_BELL, "SND_BELL"),
+    (sys::SND_TONE, "SND_TONE"),
+];
+
+const REP: &[(i32, &str)] = &[
+    (sys::REP_DELAY, "REP_DELAY"),
+    (sys::REP_PERIOD, "REP_PERIOD"),
+];
+
+/// Only the codes that are not effect ids
+const FF: &[(i32, &str)] = &[
+    (sys::FF_GAIN as i32, "FF_GAIN"),
+    (sys::FF_AUTOCENTER as i32, "FF_AUTOCENTER"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tables_are_strictly_increasing() {
+        for (name, table) in [
+            ("TYPES", TYPES),
+            ("SYN", SYN),
+            ("KEYS", KEYS),
+            ("REL", REL),
+            ("ABS", ABS),
+            ("MSC", MSC),
+            ("SW", SW),
+            ("LED", LED),
+            ("SND", SND),
+            ("REP", REP),
+            ("FF", FF),
+        ] {
+            for pair in table.windows(2) {
+                assert!(
+                    pair[0].0 < pair[1].0,
+                    "{name}: {} is not before {}",
+                    pair[0].1,
+                    pair[1].1
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn lookup_names() {
+        assert_eq!(type_name(sys::EV_KEY as u16), Some("EV_KEY"));
+        assert_eq!(code_name(sys::EV_KEY as u16, 0x110), Some("BTN_LEFT"));
+        assert_eq!(
+            code_name(sys::EV_KEY as u16, sys::KEY_AUTOPILOT_ENGAGE_TOGGLE as u16),
+            Some("KEY_AUTOPILOT_ENGAGE_TOGGLE")
+        );
+        assert_eq!(code_name(sys::EV_REL as u16, 0x7f), None);
+    }
+}
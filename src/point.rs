@@ -0,0 +1,133 @@
+use std::str::FromStr;
+
+/// A position on the output, where `0.0,0.0` is the top left corner and `1.0,1.0` the bottom right
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Point {
+    /// The point between `self` and `to`, `t` from 0.0 to 1.0
+    pub fn lerp(self, to: Point, t: f64) -> Point {
+        Point {
+            x: self.x + (to.x - self.x) * t,
+            y: self.y + (to.y - self.y) * t,
+        }
+    }
+
+    /// Moved by `dx`, `dy`, without leaving the output
+    pub fn offset(self, dx: f64, dy: f64) -> Point {
+        Point {
+            x: (self.x + dx).clamp(0.0, 1.0),
+            y: (self.y + dy).clamp(0.0, 1.0),
+        }
+    }
+}
+
+impl FromStr for Point {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((x, y)) = s.split_once(',') else {
+            return Err(format!("Expected `x,y`, got: {s:?}"));
+        };
+
+        let parse = |value: &str| {
+            let value = value
+                .trim()
+                .parse::<f64>()
+                .map_err(|err| format!("{value:?}: {err}"))?;
+            if !(0.0..=1.0).contains(&value) {
+                return Err(format!("{value} is not between 0.0 and 1.0"));
+            }
+            Ok(value)
+        };
+
+        Ok(Self {
+            x: parse(x)?,
+            y: parse(y)?,
+        })
+    }
+}
+
+impl std::fmt::Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// Where the absolute device clicks
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Target {
+    Tap(Point),
+    /// Presses at the first point, moves to the second one, then releases
+    Swipe(Point, Point),
+}
+
+impl FromStr for Target {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some((from, to)) => Ok(Self::Swipe(from.parse()?, to.parse()?)),
+            None => Ok(Self::Tap(s.parse()?)),
+        }
+    }
+}
+
+impl std::fmt::Display for Target {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Target::Tap(point) => write!(f, "{point}"),
+            Target::Swipe(from, to) => write!(f, "{from}:{to}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_targets() {
+        assert_eq!(
+            "0.5,0.25".parse::<Target>(),
+            Ok(Target::Tap(Point { x: 0.5, y: 0.25 }))
+        );
+        assert_eq!(
+            " 0 , 1 ".parse::<Target>(),
+            Ok(Target::Tap(Point { x: 0.0, y: 1.0 }))
+        );
+        assert_eq!(
+            "0.1,0.5:0.9,0.5".parse::<Target>(),
+            Ok(Target::Swipe(
+                Point { x: 0.1, y: 0.5 },
+                Point { x: 0.9, y: 0.5 }
+            ))
+        );
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for target in ["0.5,0.25", "0,1", "0.1,0.5:0.9,0.5"] {
+            assert_eq!(target.parse::<Target>().unwrap().to_string(), target);
+        }
+    }
+
+    #[test]
+    fn invalid_targets() {
+        for target in [
+            "",
+            "0.5",
+            "0.5,",
+            "x,0.5",
+            "1.5,0.5",
+            "0.5,-0.1",
+            "0.5,0.5:",
+            "0.5,0.5:0.5",
+        ] {
+            assert!(target.parse::<Target>().is_err(), "{target}");
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use nix::unistd::{Uid, User, getuid, setgroups, setresgid, setresuid};
+
+/// Switches to `user`, or to the user that ran `sudo`, once the devices are open,
+/// the open file descriptors keep working without root
+///
+/// Does nothing when not running as root, or when there is no user to switch to,
+/// returns the name of the user that it switched to
+pub fn drop_privileges(user: Option<String>) -> Option<String> {
+    if !Uid::effective().is_root() {
+        if let Some(user) = user {
+            eprintln!("\x1B[1;33mNot running as root, ignoring --user {user}\x1B[22;39m");
+        }
+        return None;
+    }
+
+    let user = match user {
+        Some(name) => match find_user(&name) {
+            Some(user) => user,
+            None => {
+                eprintln!("\x1B[1;31mNo user named {name}\x1B[22;39m");
+                std::process::exit(9);
+            }
+        },
+        None => {
+            let uid = std::env::var("SUDO_UID")
+                .ok()
+                .and_then(|uid| uid.parse().ok())?;
+            match User::from_uid(Uid::from_raw(uid)) {
+                Ok(Some(user)) => user,
+                _ => {
+                    eprintln!("\x1B[1;31mCannot find the user of SUDO_UID {uid}\x1B[22;39m");
+                    std::process::exit(9);
+                }
+            }
+        }
+    };
+    if user.uid.is_root() {
+        return None;
+    }
+
+    // The groups first, after the uid changes they cannot be changed anymore
+    let dropped = setgroups(&[])
+        .and_then(|()| setresgid(user.gid, user.gid, user.gid))
+        .and_then(|()| setresuid(user.uid, user.uid, user.uid));
+    if let Err(err) = dropped {
+        eprintln!(
+            "\x1B[1;31mCannot switch to the user {}: {err}\x1B[22;39m",
+            user.name
+        );
+        std::process::exit(9);
+    }
+    if nix::unistd::setuid(Uid::from_raw(0)).is_ok() || getuid() != user.uid {
+        eprintln!("\x1B[1;31mThe root privileges are still there!\x1B[22;39m");
+        std::process::exit(9);
+    }
+
+    println!("Running as {} from now on", user.name);
+    Some(user.name)
+}
+
+/// By name, or by uid
+fn find_user(name: &str) -> Option<User> {
+    match User::from_name(name).ok().flatten() {
+        Some(user) => Some(user),
+        None => User::from_uid(Uid::from_raw(name.parse().ok()?))
+            .ok()
+            .flatten(),
+    }
+}
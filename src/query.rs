@@ -0,0 +1,264 @@
+use std::str::FromStr;
+
+use regex::Regex;
+
+use crate::device::{DeviceInfo, DeviceType};
+
+/// How a `-d` query selects a device, other than by path
+pub enum DeviceQuery {
+    /// `046d:c52b`
+    Id { vendor: u16, product: u16 },
+    /// `phys:usb-0000:00:14.0-1`, contained in the physical location
+    Phys(String),
+    /// `uniq:<serial>`, the unique id, like the serial or the bluetooth address
+    Uniq(String),
+    /// `link:usb-Logitech_USB_Receiver-event-mouse`, the name of a `/dev/input/by-id` or `/dev/input/by-path` link
+    Link(String),
+    /// `re:<regex>`, matched on the name, in the syntax of the regex crate
+    Regex(Regex),
+    /// `type:mouse`
+    Type(DeviceType),
+    /// The name, or `name-eventN`, first looks for exact match then for the devices that contain it
+    Name(String),
+}
+
+pub enum FindError {
+    NotFound,
+    /// More devices match, the query must be more specific
+    Ambiguous(Vec<DeviceInfo>),
+}
+
+impl FromStr for DeviceQuery {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((kind, value)) = s.split_once(':') {
+            match kind {
+                "phys" => return Ok(Self::Phys(value.to_owned())),
+                "uniq" => return Ok(Self::Uniq(value.to_owned())),
+                "link" => return Ok(Self::Link(value.to_owned())),
+                "re" => {
+                    return Regex::new(value)
+                        .map(Self::Regex)
+                        .map_err(|err| err.to_string());
+                }
+                "type" => return Ok(Self::Type(value.parse()?)),
+                _ => {}
+            }
+
+            let is_id =
+                |value: &str| value.len() == 4 && value.chars().all(|c| c.is_ascii_hexdigit());
+            if is_id(kind) && is_id(value) {
+                return Ok(Self::Id {
+                    vendor: u16::from_str_radix(kind, 16).unwrap(),
+                    product: u16::from_str_radix(value, 16).unwrap(),
+                });
+            }
+        }
+
+        Ok(Self::Name(s.to_owned()))
+    }
+}
+
+impl DeviceQuery {
+    fn matches(&self, device: &DeviceInfo) -> bool {
+        match self {
+            DeviceQuery::Id { vendor, product } => {
+                device.id.vendor == *vendor && device.id.product == *product
+            }
+            DeviceQuery::Phys(phys) => !device.phys.is_empty() && device.phys.contains(phys),
+            DeviceQuery::Uniq(uniq) => device.uniq == *uniq,
+            DeviceQuery::Link(name) => device.links.iter().any(|link| {
+                link.file_name()
+                    .is_some_and(|link| link.to_string_lossy() == *name)
+            }),
+            DeviceQuery::Regex(regex) => regex.is_match(&device.name),
+            DeviceQuery::Type(device_type) => device.device_type() == *device_type,
+            DeviceQuery::Name(name) => device.display_name().trim().contains(name),
+        }
+    }
+
+    /// The only evdev device that matches, an exact name wins over the names that contain it
+    pub fn find(&self, devices: Vec<DeviceInfo>) -> Result<DeviceInfo, FindError> {
+        self.find_node(devices, DeviceInfo::is_evdev)
+    }
+
+    /// Like `find`, but only the legacy `mouseN` nodes, for `run-legacy`
+    pub fn find_legacy_mouse(&self, devices: Vec<DeviceInfo>) -> Result<DeviceInfo, FindError> {
+        self.find_node(devices, DeviceInfo::is_legacy_mouse)
+    }
+
+    fn find_node(
+        &self,
+        devices: Vec<DeviceInfo>,
+        node: fn(&DeviceInfo) -> bool,
+    ) -> Result<DeviceInfo, FindError> {
+        let mut found = devices
+            .into_iter()
+            .filter(|device| node(device) && self.matches(device))
+            .collect::<Vec<_>>();
+
+        if let DeviceQuery::Name(name) = self
+            && found.len() > 1
+        {
+            let exact = |device: &DeviceInfo| {
+                device.display_name().trim() == name || device.name.trim() == name
+            };
+            if found.iter().any(exact) {
+                found.retain(exact);
+            }
+        }
+
+        match found.len() {
+            0 => Err(FindError::NotFound),
+            1 => Ok(found.remove(0)),
+            _ => Err(FindError::Ambiguous(found)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use input_linux::InputId;
+
+    use super::*;
+    use crate::device::Capabilities;
+
+    fn device(filename: &str, name: &str) -> DeviceInfo {
+        DeviceInfo {
+            path: PathBuf::from("/dev/input").join(filename),
+            filename: filename.to_owned(),
+            name: name.to_owned(),
+            phys: "usb-0000:00:14.0-1/input0".to_owned(),
+            uniq: String::new(),
+            id: InputId {
+                bustype: 3,
+                vendor: 0x046d,
+                product: 0xc52b,
+                version: 0x111,
+            },
+            capabilities: Capabilities::default(),
+            links: Vec::new(),
+        }
+    }
+
+    fn find(query: &str, devices: Vec<DeviceInfo>) -> Result<String, usize> {
+        match query.parse::<DeviceQuery>().unwrap().find(devices) {
+            Ok(device) => Ok(device.filename),
+            Err(FindError::NotFound) => Err(0),
+            Err(FindError::Ambiguous(devices)) => Err(devices.len()),
+        }
+    }
+
+    #[test]
+    fn parse() {
+        assert!(matches!(
+            "046d:c52b".parse(),
+            Ok(DeviceQuery::Id {
+                vendor: 0x046d,
+                product: 0xc52b
+            })
+        ));
+        assert!(
+            matches!("phys:usb-0000:00:14.0-1".parse(), Ok(DeviceQuery::Phys(phys)) if phys == "usb-0000:00:14.0-1")
+        );
+        assert!(
+            matches!("uniq:c0:ff:ee".parse(), Ok(DeviceQuery::Uniq(uniq)) if uniq == "c0:ff:ee")
+        );
+        assert!(
+            matches!("link:usb-Logitech-event-mouse".parse(), Ok(DeviceQuery::Link(link)) if link == "usb-Logitech-event-mouse")
+        );
+        assert!(matches!("re:^Logi".parse(), Ok(DeviceQuery::Regex(_))));
+        assert!(matches!(
+            "type:mouse".parse(),
+            Ok(DeviceQuery::Type(DeviceType::Mouse))
+        ));
+        // Not ids, so names
+        assert!(matches!("046d:c52".parse(), Ok(DeviceQuery::Name(name)) if name == "046d:c52"));
+        assert!(
+            matches!("Razer: Mouse".parse(), Ok(DeviceQuery::Name(name)) if name == "Razer: Mouse")
+        );
+        assert!(matches!("Logitech".parse(), Ok(DeviceQuery::Name(name)) if name == "Logitech"));
+
+        assert!("re:(".parse::<DeviceQuery>().is_err());
+        assert!("type:toaster".parse::<DeviceQuery>().is_err());
+    }
+
+    #[test]
+    fn legacy_node_does_not_make_a_query_ambiguous() {
+        let devices = || {
+            vec![
+                device("event5", "Logitech USB Receiver"),
+                device("mouse1", "Logitech USB Receiver"),
+                device("js0", "Logitech USB Receiver"),
+            ]
+        };
+
+        assert_eq!(
+            find("Logitech USB Receiver", devices()),
+            Ok("event5".to_owned())
+        );
+        assert_eq!(find("Logitech", devices()), Ok("event5".to_owned()));
+        assert_eq!(find("046d:c52b", devices()), Ok("event5".to_owned()));
+        assert_eq!(
+            find("phys:usb-0000:00:14.0-1", devices()),
+            Ok("event5".to_owned())
+        );
+        assert_eq!(find("re:^Logi", devices()), Ok("event5".to_owned()));
+        assert_eq!(
+            find(r"re:^(Logitech|Razer) \w{3} Rec", devices()),
+            Ok("event5".to_owned())
+        );
+    }
+
+    #[test]
+    fn legacy_mouse_query() {
+        let devices = || {
+            vec![
+                device("event3", "PS/2 Generic Mouse"),
+                device("mouse0", "PS/2 Generic Mouse"),
+                device("js0", "PS/2 Generic Mouse"),
+            ]
+        };
+        let find_legacy_mouse = |query: &str| {
+            query
+                .parse::<DeviceQuery>()
+                .unwrap()
+                .find_legacy_mouse(devices())
+                .ok()
+                .map(|device| device.filename)
+        };
+
+        assert_eq!(find_legacy_mouse("mouse0"), Some("mouse0".to_owned()));
+        assert_eq!(
+            find_legacy_mouse("PS/2 Generic Mouse"),
+            Some("mouse0".to_owned())
+        );
+        assert_eq!(find_legacy_mouse("event3"), None);
+        assert_eq!(find("mouse0", devices()), Err(0));
+    }
+
+    #[test]
+    fn exact_name_wins() {
+        let devices = || {
+            vec![
+                device("event3", "Logitech USB Receiver"),
+                device("event4", "Logitech USB Receiver Keyboard"),
+            ]
+        };
+
+        assert_eq!(
+            find("Logitech USB Receiver", devices()),
+            Ok("event3".to_owned())
+        );
+        assert_eq!(
+            find("Logitech USB Receiver-event3", devices()),
+            Ok("event3".to_owned())
+        );
+        assert_eq!(find("Logitech", devices()), Err(2));
+        assert_eq!(find("Keyboard", devices()), Ok("event4".to_owned()));
+        assert_eq!(find("Razer", devices()), Err(0));
+    }
+}
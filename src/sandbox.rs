@@ -0,0 +1,242 @@
+use std::{
+    io,
+    os::fd::{AsRawFd, FromRawFd, OwnedFd},
+};
+
+use nix::libc::{self, c_long, sock_filter, sock_fprog};
+
+#[cfg(target_arch = "x86_64")]
+const AUDIT_ARCH: Option<u32> = Some(0xC000_003E);
+#[cfg(target_arch = "aarch64")]
+const AUDIT_ARCH: Option<u32> = Some(0xC000_00B7);
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+const AUDIT_ARCH: Option<u32> = None;
+
+/// What the event loop needs, once the devices, the virtual devices and the event loop are created:
+/// reading the input fds, writing and the ioctls of the uinput fds, arming the timer, sleeping,
+/// and what the allocator and the standard library use
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+const ALLOWED: &[c_long] = &[
+    libc::SYS_read,
+    libc::SYS_write,
+    libc::SYS_readv,
+    libc::SYS_writev,
+    libc::SYS_ioctl,
+    libc::SYS_close,
+    libc::SYS_epoll_ctl,
+    libc::SYS_epoll_pwait,
+    libc::SYS_epoll_pwait2,
+    libc::SYS_timerfd_settime,
+    libc::SYS_timerfd_gettime,
+    libc::SYS_rt_sigreturn,
+    libc::SYS_sigaltstack,
+    libc::SYS_clock_gettime,
+    libc::SYS_clock_nanosleep,
+    libc::SYS_nanosleep,
+    libc::SYS_futex,
+    libc::SYS_sched_yield,
+    libc::SYS_brk,
+    libc::SYS_mmap,
+    libc::SYS_munmap,
+    libc::SYS_mremap,
+    libc::SYS_madvise,
+    libc::SYS_getrandom,
+    libc::SYS_restart_syscall,
+    libc::SYS_exit,
+    libc::SYS_exit_group,
+    #[cfg(target_arch = "x86_64")]
+    libc::SYS_epoll_wait,
+];
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+const ALLOWED: &[c_long] = &[];
+
+const LANDLOCK_CREATE_RULESET_VERSION: u32 = 1;
+
+// The filesystem rights of `linux/landlock.h`, with the ABI that added them
+const LANDLOCK_ACCESS_FS_EXECUTE: u64 = 1 << 0;
+const LANDLOCK_ACCESS_FS_WRITE_FILE: u64 = 1 << 1;
+const LANDLOCK_ACCESS_FS_READ_FILE: u64 = 1 << 2;
+const LANDLOCK_ACCESS_FS_READ_DIR: u64 = 1 << 3;
+const LANDLOCK_ACCESS_FS_REMOVE_DIR: u64 = 1 << 4;
+const LANDLOCK_ACCESS_FS_REMOVE_FILE: u64 = 1 << 5;
+const LANDLOCK_ACCESS_FS_MAKE_CHAR: u64 = 1 << 6;
+const LANDLOCK_ACCESS_FS_MAKE_DIR: u64 = 1 << 7;
+const LANDLOCK_ACCESS_FS_MAKE_REG: u64 = 1 << 8;
+const LANDLOCK_ACCESS_FS_MAKE_SOCK: u64 = 1 << 9;
+const LANDLOCK_ACCESS_FS_MAKE_FIFO: u64 = 1 << 10;
+const LANDLOCK_ACCESS_FS_MAKE_BLOCK: u64 = 1 << 11;
+const LANDLOCK_ACCESS_FS_MAKE_SYM: u64 = 1 << 12;
+/// ABI 2
+const LANDLOCK_ACCESS_FS_REFER: u64 = 1 << 13;
+/// ABI 3
+const LANDLOCK_ACCESS_FS_TRUNCATE: u64 = 1 << 14;
+/// ABI 5, ABI 4 only added the network rights
+const LANDLOCK_ACCESS_FS_IOCTL_DEV: u64 = 1 << 15;
+
+/// Every right of ABI 1
+const LANDLOCK_ACCESS_FS_V1: u64 = LANDLOCK_ACCESS_FS_EXECUTE
+    | LANDLOCK_ACCESS_FS_WRITE_FILE
+    | LANDLOCK_ACCESS_FS_READ_FILE
+    | LANDLOCK_ACCESS_FS_READ_DIR
+    | LANDLOCK_ACCESS_FS_REMOVE_DIR
+    | LANDLOCK_ACCESS_FS_REMOVE_FILE
+    | LANDLOCK_ACCESS_FS_MAKE_CHAR
+    | LANDLOCK_ACCESS_FS_MAKE_DIR
+    | LANDLOCK_ACCESS_FS_MAKE_REG
+    | LANDLOCK_ACCESS_FS_MAKE_SOCK
+    | LANDLOCK_ACCESS_FS_MAKE_FIFO
+    | LANDLOCK_ACCESS_FS_MAKE_BLOCK
+    | LANDLOCK_ACCESS_FS_MAKE_SYM;
+
+/// `struct landlock_ruleset_attr` of the first ABI, the kernel accepts the shorter struct
+#[repr(C)]
+struct LandlockRulesetAttr {
+    handled_access_fs: u64,
+}
+
+/// Forbids opening any file, and every syscall that the event loop does not need,
+/// the already open file descriptors keep working
+///
+/// Without Landlock only the syscalls are filtered, a failing filter stops TheClicker
+pub fn sandbox() {
+    let Some(arch) = AUDIT_ARCH else {
+        eprintln!("\x1B[1;31mThe sandbox is only supported on x86_64 and aarch64\x1B[22;39m");
+        std::process::exit(11);
+    };
+
+    // Both need it without CAP_SYS_ADMIN, and it keeps a setuid binary from regaining root
+    if unsafe { libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) } != 0 {
+        eprintln!(
+            "\x1B[1;31mCannot set no_new_privs: {}\x1B[22;39m",
+            io::Error::last_os_error()
+        );
+        std::process::exit(11);
+    }
+
+    match landlock() {
+        Ok(abi) => println!("Landlock ABI {abi}: no more files can be opened"),
+        Err(err) => eprintln!(
+            "\x1B[1;33mLandlock is not available, only the syscalls are filtered: {err}\x1B[22;39m"
+        ),
+    }
+
+    if let Err(err) = seccomp(arch) {
+        eprintln!("\x1B[1;31mCannot filter the syscalls: {err}\x1B[22;39m");
+        std::process::exit(11);
+    }
+    println!(
+        "Seccomp: only the {} syscalls of the event loop are allowed",
+        ALLOWED.len()
+    );
+}
+
+/// A ruleset that handles every filesystem access and allows none
+fn landlock() -> io::Result<i64> {
+    let abi = unsafe {
+        libc::syscall(
+            libc::SYS_landlock_create_ruleset,
+            std::ptr::null::<LandlockRulesetAttr>(),
+            0usize,
+            LANDLOCK_CREATE_RULESET_VERSION,
+        )
+    };
+    if abi < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    // Every ABI adds rights, handling one that the kernel does not know is an error.
+    // The rights are checked when a file is opened, so IOCTL_DEV does not affect the open devices
+    let handled_access_fs = match abi {
+        1 => LANDLOCK_ACCESS_FS_V1,
+        2 => LANDLOCK_ACCESS_FS_V1 | LANDLOCK_ACCESS_FS_REFER,
+        3 | 4 => LANDLOCK_ACCESS_FS_V1 | LANDLOCK_ACCESS_FS_REFER | LANDLOCK_ACCESS_FS_TRUNCATE,
+        _ => {
+            LANDLOCK_ACCESS_FS_V1
+                | LANDLOCK_ACCESS_FS_REFER
+                | LANDLOCK_ACCESS_FS_TRUNCATE
+                | LANDLOCK_ACCESS_FS_IOCTL_DEV
+        }
+    };
+    let attr = LandlockRulesetAttr { handled_access_fs };
+    let fd = unsafe {
+        libc::syscall(
+            libc::SYS_landlock_create_ruleset,
+            &attr,
+            size_of::<LandlockRulesetAttr>(),
+            0u32,
+        )
+    };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let ruleset = unsafe { OwnedFd::from_raw_fd(fd as i32) };
+
+    if unsafe { libc::syscall(libc::SYS_landlock_restrict_self, ruleset.as_raw_fd(), 0u32) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(abi)
+}
+
+fn statement(code: u32, k: u32) -> sock_filter {
+    sock_filter {
+        code: code as u16,
+        jt: 0,
+        jf: 0,
+        k,
+    }
+}
+
+fn jump(code: u32, k: u32, jt: u8, jf: u8) -> sock_filter {
+    sock_filter {
+        code: code as u16,
+        jt,
+        jf,
+        k,
+    }
+}
+
+/// Kills on another architecture, the other syscalls fail with EPERM
+fn seccomp(arch: u32) -> io::Result<()> {
+    let arch_offset = std::mem::offset_of!(libc::seccomp_data, arch) as u32;
+    let nr_offset = std::mem::offset_of!(libc::seccomp_data, nr) as u32;
+
+    let mut filter = vec![
+        statement(libc::BPF_LD | libc::BPF_W | libc::BPF_ABS, arch_offset),
+        jump(libc::BPF_JMP | libc::BPF_JEQ | libc::BPF_K, arch, 1, 0),
+        statement(libc::BPF_RET | libc::BPF_K, libc::SECCOMP_RET_KILL_PROCESS),
+        statement(libc::BPF_LD | libc::BPF_W | libc::BPF_ABS, nr_offset),
+    ];
+    for nr in ALLOWED {
+        filter.push(jump(
+            libc::BPF_JMP | libc::BPF_JEQ | libc::BPF_K,
+            *nr as u32,
+            0,
+            1,
+        ));
+        filter.push(statement(
+            libc::BPF_RET | libc::BPF_K,
+            libc::SECCOMP_RET_ALLOW,
+        ));
+    }
+    filter.push(statement(
+        libc::BPF_RET | libc::BPF_K,
+        libc::SECCOMP_RET_ERRNO | (libc::EPERM as u32 & libc::SECCOMP_RET_DATA),
+    ));
+
+    let program = sock_fprog {
+        len: filter.len() as u16,
+        filter: filter.as_mut_ptr(),
+    };
+    let res = unsafe {
+        libc::syscall(
+            libc::SYS_seccomp,
+            libc::SECCOMP_SET_MODE_FILTER,
+            0u32,
+            &program,
+        )
+    };
+    if res != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
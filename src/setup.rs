@@ -0,0 +1,151 @@
+use std::{fs, path::Path, process::Command};
+
+use nix::unistd::{Gid, Group, Uid, User};
+
+use crate::{device::DeviceInfo, device_info_from_query};
+
+/// Writes, or prints, the udev rule that gives the group access to `/dev/uinput`,
+/// and the user of the seat access to the device of the query
+pub fn udev(device_query: Option<String>, group: Option<String>, print: bool, path: &Path) {
+    let group = match group {
+        Some(name) => match Group::from_name(&name) {
+            Ok(Some(group)) => group,
+            _ => {
+                eprintln!("\x1B[1;31mNo group named {name}\x1B[22;39m");
+                std::process::exit(1);
+            }
+        },
+        None => {
+            let user = invoking_user();
+            match Group::from_gid(user.gid) {
+                Ok(Some(group)) => group,
+                _ => {
+                    eprintln!(
+                        "\x1B[1;31mCannot find the group {} of {}, use --group\x1B[22;39m",
+                        user.gid, user.name
+                    );
+                    std::process::exit(1);
+                }
+            }
+        }
+    };
+    if group.gid == Gid::from_raw(0) {
+        eprintln!(
+            "\x1B[1;31mThe rule would only give the root group access, run with sudo from your user or use --group\x1B[22;39m"
+        );
+        std::process::exit(1);
+    }
+
+    let device = device_query.map(|device_query| device_info_from_query(&device_query));
+    let rule = rule(&group.name, device.as_ref());
+
+    if print {
+        print!("{rule}");
+        eprintln!(
+            "\x1B[1;33mWrite it to {}, then run: sudo udevadm control --reload-rules && sudo udevadm trigger\x1B[22;39m",
+            path.display()
+        );
+        return;
+    }
+
+    if let Err(err) = fs::write(path, &rule) {
+        eprintln!(
+            "\x1B[1;31mCannot write {}: {err}, run with sudo or use --print\x1B[22;39m",
+            path.display()
+        );
+        std::process::exit(1);
+    }
+    println!("Wrote {}:", path.display());
+    print!("{rule}");
+
+    let mut reloaded = udevadm(&["control", "--reload-rules"])
+        && udevadm(&[
+            "trigger",
+            "--action=change",
+            "--subsystem-match=misc",
+            "--sysname-match=uinput",
+        ]);
+    if device.is_some() {
+        reloaded &= udevadm(&["trigger", "--action=change", "--subsystem-match=input"]);
+    }
+    if !reloaded {
+        eprintln!(
+            "\x1B[1;33mCannot reload the udev rules, run: sudo udevadm control --reload-rules && sudo udevadm trigger\x1B[22;39m"
+        );
+    }
+
+    println!(
+        "\x1B[1;32mDone, members of the {} group can create the virtual devices\x1B[22;39m",
+        group.name
+    );
+    if let Some(device) = device {
+        println!(
+            "\x1B[1;32mThe user logged in on the seat can read {}\x1B[22;39m",
+            device.display_name()
+        );
+    }
+}
+
+/// The user that ran `sudo`, or the current user
+fn invoking_user() -> User {
+    let uid = std::env::var("SUDO_UID")
+        .ok()
+        .and_then(|uid| uid.parse().ok())
+        .map(Uid::from_raw)
+        .unwrap_or(Uid::current());
+    match User::from_uid(uid) {
+        Ok(Some(user)) => user,
+        _ => {
+            eprintln!("\x1B[1;31mCannot find the user {uid}, use --group\x1B[22;39m");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// `TAG+="uaccess"` only gives access to the user of the local seat,
+/// the file must sort before `73-seat-late.rules` that applies it
+fn rule(group: &str, device: Option<&DeviceInfo>) -> String {
+    let mut rule = String::from("# Written by `theclicker setup udev`\n");
+    rule += &format!("# The {group} group can create the virtual devices\n");
+    rule += &format!(
+        "KERNEL==\"uinput\", SUBSYSTEM==\"misc\", OPTIONS+=\"static_node=uinput\", GROUP=\"{}\", MODE=\"0660\"\n",
+        escape(group)
+    );
+
+    if let Some(device) = device {
+        rule += &format!(
+            "# The user of the seat can read {}, and no other input device\n",
+            device.display_name()
+        );
+        rule += &format!(
+            "SUBSYSTEM==\"input\", KERNEL==\"event*\", ATTRS{{id/vendor}}==\"{:04x}\", ATTRS{{id/product}}==\"{:04x}\", ATTRS{{name}}==\"{}\"",
+            device.id.vendor,
+            device.id.product,
+            escape(&device.name)
+        );
+        if !device.uniq.is_empty() {
+            rule += &format!(", ATTRS{{uniq}}==\"{}\"", escape(&device.uniq));
+        }
+        rule += ", TAG+=\"uaccess\"\n";
+    }
+
+    rule
+}
+
+/// The values are globs without escapes, `?` matches the characters that would end or change them
+fn escape(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match c {
+            '"' | '\\' | '*' | '?' | '[' | ']' | '|' => '?',
+            c => c,
+        })
+        .collect()
+}
+
+fn udevadm(args: &[&str]) -> bool {
+    Command::new("udevadm")
+        .args(args)
+        .status()
+        .is_ok_and(|status| status.success())
+}
@@ -0,0 +1,101 @@
+#!/usr/bin/env python3
+"""Regenerates the tables of src/names.rs from the constants of input-linux-sys.
+
+Usage, from the root of the repository:
+
+    python3 tools/names.py [path/to/input-linux-sys/src] && cargo fmt
+
+Without a path, the newest input-linux-sys in ~/.cargo/registry/src is used,
+run `cargo fetch` first. The tables between `const TYPES` and `#[cfg(test)]`
+are replaced, the rest of src/names.rs is kept.
+
+Only the `pub const NAME: c_int = <number>;` lines are used, so the aliases,
+that are defined as another constant, are left out, like the `_MAX` and `_CNT`.
+When more names have the same value the last one is kept.
+"""
+
+import glob
+import os
+import re
+import sys
+
+# The table, the prefixes of its constants
+TABLES = [
+    ("TYPES", ["EV"]),
+    ("SYN", ["SYN"]),
+    ("KEYS", ["KEY", "BTN"]),
+    ("REL", ["REL"]),
+    ("ABS", ["ABS"]),
+    ("MSC", ["MSC"]),
+    ("SW", ["SW"]),
+    ("LED", ["LED"]),
+    ("SND", ["SND"]),
+    ("REP", ["REP"]),
+]
+
+# The force-feedback codes are `u16`, and below FF_GAIN they are effect ids
+FF = ["FF_GAIN", "FF_AUTOCENTER"]
+
+NAMES = os.path.join(os.path.dirname(__file__), "..", "src", "names.rs")
+
+
+def source_dir():
+    if len(sys.argv) > 1:
+        return sys.argv[1]
+    dirs = glob.glob(
+        os.path.expanduser("~/.cargo/registry/src/*/input-linux-sys-*/src")
+    )
+    if not dirs:
+        sys.exit("input-linux-sys not found, run `cargo fetch` or pass its src directory")
+    version = lambda path: [
+        int(part) for part in re.findall(r"input-linux-sys-([\d.]+)", path)[0].split(".")
+    ]
+    return max(dirs, key=version)
+
+
+def constants(events):
+    found = []
+    pattern = r"^pub const (\w+):\s*c_int\s*=\s*(0x[0-9a-fA-F]+|\d+);"
+    for match in re.finditer(pattern, events, re.M):
+        name, value = match.group(1), int(match.group(2), 0)
+        if name.endswith("_MAX") or name.endswith("_CNT"):
+            continue
+        found.append((name, value))
+    return found
+
+
+def table(name, rows, cast="", doc=None):
+    lines = [] if doc is None else [f"/// {doc}"]
+    lines.append(f"const {name}: &[(i32, &str)] = &[")
+    for row in rows:
+        lines.append(f'    (sys::{row}{cast}, "{row}"),')
+    lines.append("];")
+    return "\n".join(lines)
+
+
+def main():
+    with open(os.path.join(source_dir(), "events.rs")) as file:
+        found = constants(file.read())
+
+    tables = []
+    for name, prefixes in TABLES:
+        by_value = {}
+        for constant, value in found:
+            if constant.split("_")[0] in prefixes:
+                by_value[value] = constant
+        tables.append(table(name, [by_value[value] for value in sorted(by_value)]))
+    tables.append(
+        table("FF", FF, cast=" as i32", doc="Only the codes that are not effect ids")
+    )
+
+    with open(NAMES) as file:
+        names = file.read()
+    start = names.index("const TYPES")
+    end = names.index("#[cfg(test)]")
+    names = names[:start] + "\n\n".join(tables) + "\n\n" + names[end:]
+    with open(NAMES, "w") as file:
+        file.write(names)
+
+
+if __name__ == "__main__":
+    main()